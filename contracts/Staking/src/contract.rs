@@ -3,19 +3,21 @@ use cosmwasm_std::entry_point;
 
 use cosmwasm_std::{
     Addr, to_binary, DepsMut, Env, MessageInfo, Response,
-    Uint128, CosmosMsg, WasmMsg, Storage
+    Uint128, CosmosMsg, WasmMsg, Storage, Order, StdResult
 };
 use cw2::set_contract_version;
 use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse, TokenInfoResponse};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, UserInfo, CardInfo, CardType};
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, UserInfo, CardInfo, CardType, PendingConfig};
 use crate::state::{USER_INFOS, CARD_INFOS, OWNER, REWARD_TOKEN, START_TIME, 
-    PLATIUM_CARD_NUMBER, GOLD_CARD_NUMBER, SILVER_CARD_NUMBER, BRONZE_CARD_NUMBER};
+    PLATIUM_CARD_NUMBER, GOLD_CARD_NUMBER, SILVER_CARD_NUMBER, BRONZE_CARD_NUMBER,
+    PENDING_OWNER, TIMELOCK, PENDING_CONFIG, TOTAL_STAKED, TOTAL_KEY};
 use crate::util::{check_onlyowner, get_cardtype, manage_card, get_reward,
-        update_userinfo, get_token_balance};
+        update_userinfo, get_token_balance, snapshot_stake};
 
 const WFD_TOKEN: &str = "terra1pkytkcanua4uazlpekve7qyhg2c5xwwjr4429d";
+const DEFAULT_TIMELOCK: u128 = 172800; //2 days
 
 // version info for migration info
 const CONTRACT_NAME: &str = "Staking";
@@ -48,16 +50,40 @@ pub fn instantiate(
     };
     START_TIME.save(deps.storage, &start_time)?;
 
+    let timelock = msg.timelock.unwrap_or(Uint128::from(DEFAULT_TIMELOCK));
+    TIMELOCK.save(deps.storage, &timelock)?;
+
     CARD_INFOS.save(deps.storage, &Vec::new())?;
     PLATIUM_CARD_NUMBER.save(deps.storage, &Uint128::zero())?;
     GOLD_CARD_NUMBER.save(deps.storage, &Uint128::zero())?;
     SILVER_CARD_NUMBER.save(deps.storage, &Uint128::zero())?;
     BRONZE_CARD_NUMBER.save(deps.storage, &Uint128::zero())?;
+    TOTAL_STAKED.save(deps.storage, TOTAL_KEY, &Uint128::zero(), env.block.height)?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate"))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    //stakes from before snapshots were kept, recorded once at this height
+    if TOTAL_STAKED.may_load(deps.storage, TOTAL_KEY)?.is_none() {
+        let users: Vec<UserInfo> = USER_INFOS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, user)| user))
+            .collect::<StdResult<_>>()?;
+        TOTAL_STAKED.save(deps.storage, TOTAL_KEY, &Uint128::zero(), env.block.height)?;
+        for user in users {
+            snapshot_stake(deps.storage, user.wallet, user.amount, env.block.height)?;
+        }
+    }
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate"))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -66,8 +92,23 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::SetConfig{ owner, start_time, reward_token}
-            => try_setconfig(deps, info, owner, start_time, reward_token),
+        ExecuteMsg::SetConfig{ start_time, reward_token, timelock }
+            => try_setconfig(deps, env, info, start_time, reward_token, timelock),
+
+        ExecuteMsg::ExecuteConfig{ }
+            => try_executeconfig(deps, env, info),
+
+        ExecuteMsg::CancelConfig{ }
+            => try_cancelconfig(deps, info),
+
+        ExecuteMsg::ProposeOwner{ owner }
+            => try_proposeowner(deps, info, owner),
+
+        ExecuteMsg::AcceptOwnership{ }
+            => try_acceptownership(deps, info),
+
+        ExecuteMsg::CancelOwnershipProposal{ }
+            => try_cancelownershipproposal(deps, info),
 
         ExecuteMsg::Deposit { wallet, amount }
             => try_deposit(deps, env, info, wallet, amount),
@@ -82,22 +123,16 @@ pub fn execute(
 
 pub fn try_setconfig(
     deps:DepsMut, 
+    env:Env,
     info:MessageInfo, 
-    _owner: Option<Addr>,
     _start_time: Option<Uint128>,
-    _reward_token: Option<Addr>
+    _reward_token: Option<Addr>,
+    _timelock: Option<Uint128>
 )
     -> Result<Response, ContractError>
 {
     check_onlyowner(deps.storage, info.sender.clone())?;
 
-    let mut owner = OWNER.load(deps.storage)?;
-    owner = match _owner{
-        Some(admin) => admin,
-        None => owner
-    };
-    OWNER.save(deps.storage, &owner)?;
-
     let mut start_time = START_TIME.load(deps.storage)?;
     start_time = match _start_time{
         Some(time) => time,
@@ -105,15 +140,105 @@ pub fn try_setconfig(
     };
     START_TIME.save(deps.storage, &start_time)?;
 
-    let mut reward_token = REWARD_TOKEN.load(deps.storage)?;
-    reward_token = match _reward_token{
-        Some(token) => token,
-        None => reward_token
+    //--------reward token and timelock wait for current timelock---------
+    if _reward_token == None && _timelock == None {
+        return Ok(Response::new()
+            .add_attribute("action", "SetConfig"));
+    }
+
+    //--------contracts instantiated before the timelock have none stored------
+    let timelock = TIMELOCK.may_load(deps.storage)?.unwrap_or(Uint128::from(DEFAULT_TIMELOCK));
+    let reward_token = match _reward_token{
+        Some(token) => deps.api.addr_validate(token.as_str())?,
+        None => REWARD_TOKEN.load(deps.storage)?
     };
-    REWARD_TOKEN.save(deps.storage, &reward_token)?;
+    let execute_after = Uint128::from(env.block.time.seconds()) + timelock;
+    PENDING_CONFIG.save(deps.storage, &PendingConfig{
+        reward_token,
+        timelock: _timelock.unwrap_or(timelock),
+        execute_after,
+    })?;
+
+    Ok(Response::new()
+        .add_attribute("action", "SetConfig")
+        .add_attribute("execute_after", execute_after))
+}
+
+pub fn try_executeconfig(deps:DepsMut, env:Env, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    let pending = PENDING_CONFIG.may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingConfig{})?;
+    if Uint128::from(env.block.time.seconds()) < pending.execute_after {
+        return Err(ContractError::TimelockNotExpired{ execute_after: pending.execute_after });
+    }
+
+    REWARD_TOKEN.save(deps.storage, &pending.reward_token)?;
+    TIMELOCK.save(deps.storage, &pending.timelock)?;
+    PENDING_CONFIG.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "ExecuteConfig"))
+}
+
+pub fn try_cancelconfig(deps:DepsMut, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    if PENDING_CONFIG.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingConfig{});
+    }
+    PENDING_CONFIG.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "CancelConfig"))
+}
+
+pub fn try_proposeowner(deps:DepsMut, info:MessageInfo, owner: String)
+    -> Result<Response, ContractError>
+{
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    let owner = deps.api.addr_validate(&owner)?;
+    PENDING_OWNER.save(deps.storage, &owner)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "ProposeOwner")
+        .add_attribute("owner", owner))
+}
+
+pub fn try_acceptownership(deps:DepsMut, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    let pending = PENDING_OWNER.may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner{})?;
+    if info.sender != pending {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    OWNER.save(deps.storage, &pending)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "AcceptOwnership")
+        .add_attribute("owner", pending))
+}
+
+pub fn try_cancelownershipproposal(deps:DepsMut, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner{});
+    }
+    PENDING_OWNER.remove(deps.storage);
 
     Ok(Response::new()
-        .add_attribute("action", "SetConfig"))                                
+        .add_attribute("action", "CancelOwnershipProposal"))
 }
 
 pub fn try_deposit(
@@ -155,6 +280,7 @@ pub fn try_deposit(
     )?;
     user_info.card_type = card_type.clone();
 
+    snapshot_stake(deps.storage, wallet.clone(), user_info.amount, env.block.height)?;
     USER_INFOS.save(deps.storage, wallet, &user_info)?;
     Ok(Response::new()
         .add_attribute("action", "desposit"))
@@ -192,6 +318,7 @@ pub fn try_withdraw(
     )?;
     user_info.card_type = card_type.clone();
     user_info.last_withdraw_time = Uint128::from(env.block.time.seconds() as u128);
+    snapshot_stake(deps.storage, wallet.clone(), user_info.amount, env.block.height)?;
     USER_INFOS.save(deps.storage, wallet.clone(), &user_info)?;
 
     let msg = WasmMsg::Execute { 
@@ -18,6 +18,15 @@ pub enum ContractError {
 
     #[error("Staking error: {:?}", msg)]
     StakingError {msg: String},
+
+    #[error("No config change queued")]
+    NoPendingConfig {},
+
+    #[error("Timelock not expired, change executable after {:?}", execute_after)]
+    TimelockNotExpired {execute_after: Uint128},
+
+    #[error("No ownership transfer proposed")]
+    NoPendingOwner {},
     
     #[error("Not correct Milestone status : {aust_balance} {estimate_exchange_rate} {epoch_exchange_rate} {withdraw_amount} {release_amount}")]
     Testing{
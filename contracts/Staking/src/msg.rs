@@ -7,17 +7,28 @@ use cw_storage_plus::{Map, U128Key};
 pub struct InstantiateMsg {
     pub owner: Option<String>,
     pub start_time: Option<Uint128>,
-    pub reward_token: Option<String>
+    pub reward_token: Option<String>,
+    pub timelock: Option<Uint128>
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
     SetConfig {
-        owner: Option<Addr>,
         start_time: Option<Uint128>,
-        reward_token: Option<Addr>
+        reward_token: Option<Addr>,
+        timelock: Option<Uint128>
+    },
+    ExecuteConfig {},
+    CancelConfig {},
+    ProposeOwner {
+        owner: String,
     },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
     Deposit {
         wallet: Addr,
         amount: Uint128,
@@ -35,11 +46,22 @@ pub enum ExecuteMsg {
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetOwner{ },
+    GetPendingOwner{ },
+    GetPendingConfig{ },
     GetTokenAddress{ },
     GetStartTime{ },
     GetUserInfo{ wallet: Addr },
     GetPendingRewards{ wallet: Addr },
-    GetCardInfo{ }
+    GetCardInfo{ },
+    GetStakeAt{ wallet: Addr, height: u64 },
+    GetTotalStakeAt{ height: u64 }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfig{
+    pub reward_token: Addr,
+    pub timelock: Uint128,
+    pub execute_after: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -9,7 +9,8 @@ use cosmwasm_std::{
 use cw20::{ Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse, TokenInfoResponse };
 
 use crate::msg::{QueryMsg, UserInfo};
-use crate::state::{ OWNER, REWARD_TOKEN, USER_INFOS, CARD_INFOS, START_TIME};
+use crate::state::{ OWNER, REWARD_TOKEN, USER_INFOS, CARD_INFOS, START_TIME,
+    PENDING_OWNER, PENDING_CONFIG, STAKES, TOTAL_STAKED, TOTAL_KEY};
 use crate::util::{ get_reward };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -19,6 +20,14 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&OWNER.load(deps.storage)?)
         },
 
+        QueryMsg::GetPendingOwner{ } => {
+            to_binary(&PENDING_OWNER.may_load(deps.storage)?)
+        },
+
+        QueryMsg::GetPendingConfig{ } => {
+            to_binary(&PENDING_CONFIG.may_load(deps.storage)?)
+        },
+
         QueryMsg::GetTokenAddress{ } => {
             to_binary(&REWARD_TOKEN.load(deps.storage)?)
         },
@@ -44,6 +53,16 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
         QueryMsg::GetCardInfo{ } => {
             to_binary(&CARD_INFOS.load(deps.storage)?)
+        },
+
+        QueryMsg::GetStakeAt{ wallet, height } => {
+            let stake = STAKES.may_load_at_height(deps.storage, wallet, height)?;
+            to_binary(&stake.unwrap_or_default())
+        },
+
+        QueryMsg::GetTotalStakeAt{ height } => {
+            let total = TOTAL_STAKED.may_load_at_height(deps.storage, TOTAL_KEY, height)?;
+            to_binary(&total.unwrap_or_default())
         }
 
     }
@@ -1,14 +1,23 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use cosmwasm_std::{Addr, Uint128, Coin, StdResult, DepsMut};
-use cw_storage_plus::{Item, Map, U128Key};
-use crate::msg::{UserInfo, CardInfo, CardType};
+use cw_storage_plus::{Item, Map, U128Key, SnapshotMap, Strategy};
+use crate::msg::{UserInfo, CardInfo, CardType, PendingConfig};
 
 pub const OWNER: Item<Addr> = Item::new("owner");
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
 pub const START_TIME: Item<Uint128> = Item::new("start_time");
 pub const REWARD_TOKEN: Item<Addr> = Item::new("reward_token");
+pub const TIMELOCK: Item<Uint128> = Item::new("timelock");
+pub const PENDING_CONFIG: Item<PendingConfig> = Item::new("pending_config");
 
 pub const USER_INFOS: Map<Addr, UserInfo> = Map::new("user infos");
+//staked amount per wallet and in total, readable at any past height
+pub const STAKES: SnapshotMap<Addr, Uint128> = SnapshotMap::new(
+    "stakes", "stakes__checkpoints", "stakes__changelog", Strategy::EveryBlock);
+pub const TOTAL_STAKED: SnapshotMap<&str, Uint128> = SnapshotMap::new(
+    "total_staked", "total_staked__checkpoints", "total_staked__changelog", Strategy::EveryBlock);
+pub const TOTAL_KEY: &str = "total";
 pub const CARD_INFOS: Item<Vec<CardInfo>> = Item::new("card infos");
 
 pub const PLATIUM_CARD_NUMBER: Item<Uint128> = Item::new("platium card NUMBER");
@@ -28,7 +28,8 @@ fn workflow(){
     let msg = InstantiateMsg{
         owner: Some(String::from("owner")),
         start_time: Some(Uint128::from(1u128)),
-        reward_token: Some(String::from("wfd"))
+        reward_token: Some(String::from("wfd")),
+        timelock: None
     };
 //instantiate
     let info = mock_info("owner", &[]);
@@ -37,9 +38,9 @@ fn workflow(){
 //set starttime
     let info = mock_info("owner", &[]);
     let msg = ExecuteMsg::SetConfig { 
-        owner: None,
         start_time: Some(Uint128::from(10u128)),
-        reward_token: None
+        reward_token: None,
+        timelock: None
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
     println!("SetConfig{:?}", res);
@@ -111,3 +112,58 @@ fn workflow(){
     println!("Card info {:?}", res );    
 }
 
+
+#[test]
+fn stake_snapshots(){
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[
+        (
+            &"wfd".to_string(), 
+            &[(&MOCK_CONTRACT_ADDR.to_string(),&Uint128::from(1_000_000_000u128))]
+        )
+    ]);
+    let msg = InstantiateMsg{
+        owner: Some(String::from("owner")),
+        start_time: Some(Uint128::from(1u128)),
+        reward_token: Some(String::from("wfd")),
+        timelock: None
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("owner", &[]), msg).unwrap();
+
+    let user1 = Addr::unchecked("user1".to_string());
+    let user2 = Addr::unchecked("user2".to_string());
+    let mut env = mock_env();
+    let opened = env.block.height + 1;
+
+//both stake before the vote opens
+    let msg = ExecuteMsg::Deposit{ wallet: user1.clone(), amount: Uint128::from(3_000u128) };
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Deposit{ wallet: user2.clone(), amount: Uint128::from(1_000u128) };
+    execute(deps.as_mut(), env.clone(), mock_info("user2", &[]), msg).unwrap();
+
+//user1 moves stake to user2 after it opened
+    env.block.height = opened + 1;
+    let msg = ExecuteMsg::Withdraw{ wallet: user1.clone(), amount: Uint128::from(3_000u128) };
+    execute(deps.as_mut(), env.clone(), mock_info("user1", &[]), msg).unwrap();
+    let msg = ExecuteMsg::Deposit{ wallet: user2.clone(), amount: Uint128::from(3_000u128) };
+    execute(deps.as_mut(), env.clone(), mock_info("user2", &[]), msg).unwrap();
+
+//stake at opening is unchanged
+    let msg = QueryMsg::GetStakeAt{ wallet: user1.clone(), height: opened };
+    let stake: Uint128 = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(stake, Uint128::from(3_000u128));
+    let msg = QueryMsg::GetStakeAt{ wallet: user2.clone(), height: opened };
+    let stake: Uint128 = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(stake, Uint128::from(1_000u128));
+    let msg = QueryMsg::GetTotalStakeAt{ height: opened };
+    let total: Uint128 = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(total, Uint128::from(4_000u128));
+
+//and current stake follows the move
+    let msg = QueryMsg::GetStakeAt{ wallet: user1, height: opened + 2 };
+    let stake: Uint128 = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(stake, Uint128::zero());
+    let msg = QueryMsg::GetTotalStakeAt{ height: opened + 2 };
+    let total: Uint128 = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(total, Uint128::from(4_000u128));
+}
@@ -5,6 +5,7 @@ use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse,
 
 use crate::state::{ OWNER, PLATIUM_CARD_NUMBER, GOLD_CARD_NUMBER, SILVER_CARD_NUMBER,
     BRONZE_CARD_NUMBER, CARD_INFOS, USER_INFOS, REWARD_TOKEN, START_TIME,
+    STAKES, TOTAL_STAKED, TOTAL_KEY,
 };
 use crate::msg::{CardType, CardInfo};
 
@@ -16,6 +17,16 @@ pub fn check_onlyowner(storage: &dyn Storage, sender: Addr) -> Result<Response,
     Ok(Response::new())
 }
 
+//record new stake of wallet at height, total moves by the difference
+pub fn snapshot_stake(storage: &mut dyn Storage, wallet: Addr, amount: Uint128, height: u64)
+    -> StdResult<()>
+{
+    let old = STAKES.may_load(storage, wallet.clone())?.unwrap_or_default();
+    let total = TOTAL_STAKED.may_load(storage, TOTAL_KEY)?.unwrap_or_default();
+    STAKES.save(storage, wallet, &amount, height)?;
+    TOTAL_STAKED.save(storage, TOTAL_KEY, &(total.checked_sub(old)? + amount), height)
+}
+
 pub fn get_cardtype(storage: &dyn Storage, amount: Uint128) -> StdResult<CardType>
 {
     let platium = Uint128::from(100_000u128);
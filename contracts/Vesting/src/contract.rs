@@ -10,7 +10,7 @@ use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse,
 
 use crate::error::ContractError;
 use crate::msg::{ExecuteMsg, InstantiateMsg, ProjectInfo, UserInfo, VestingParameter, Config};
-use crate::state::{PROJECT_INFOS, OWNER};
+use crate::state::{PROJECT_INFOS, OWNER, PENDING_OWNER};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "Vesting";
@@ -43,8 +43,14 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::SetConfig{ admin }
-            => try_setconfig(deps, info, admin),
+        ExecuteMsg::ProposeOwner{ owner }
+            => try_proposeowner(deps, info, owner),
+
+        ExecuteMsg::AcceptOwnership{ }
+            => try_acceptownership(deps, info),
+
+        ExecuteMsg::CancelOwnershipProposal{ }
+            => try_cancelownershipproposal(deps, info),
 
         ExecuteMsg::StartRelease{ project_id, start_time }
             => try_startrelease(deps, info, project_id, start_time),
@@ -325,18 +331,55 @@ pub fn try_addproject(deps:DepsMut, info:MessageInfo,
     Ok(Response::new()
         .add_attribute("action", "add project"))                                
 }
-pub fn try_setconfig(deps:DepsMut, info:MessageInfo, admin: String) 
+pub fn try_proposeowner(deps:DepsMut, info:MessageInfo, owner: String) 
     -> Result<Response, ContractError>
 {
-    // //-----------check owner--------------------------
-    // let owner = OWNER.load(deps.storage).unwrap();
-    // if info.sender != owner {
-    //     return Err(ContractError::Unauthorized{});
-    // }
+    //-----------check owner--------------------------
+    let current = OWNER.load(deps.storage).unwrap();
+    if info.sender != current {
+        return Err(ContractError::Unauthorized{});
+    }
 
-    let admin_addr = deps.api.addr_validate(&admin).unwrap();
-    OWNER.save(deps.storage, &admin_addr)?;
+    let owner_addr = deps.api.addr_validate(&owner)?;
+    PENDING_OWNER.save(deps.storage, &owner_addr)?;
 
     Ok(Response::new()
-        .add_attribute("action", "SetConfig"))                                
+        .add_attribute("action", "Propose owner")
+        .add_attribute("owner", owner_addr))
+}
+
+pub fn try_acceptownership(deps:DepsMut, info:MessageInfo) 
+    -> Result<Response, ContractError>
+{
+    //-----------only proposed owner accepts------------
+    let pending = PENDING_OWNER.may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner{})?;
+    if info.sender != pending {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    OWNER.save(deps.storage, &pending)?;
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "Accept ownership")
+        .add_attribute("owner", pending))
+}
+
+pub fn try_cancelownershipproposal(deps:DepsMut, info:MessageInfo) 
+    -> Result<Response, ContractError>
+{
+    //-----------check owner--------------------------
+    let owner = OWNER.load(deps.storage).unwrap();
+    if info.sender != owner {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner{});
+    }
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "Cancel ownership proposal"))
 }
@@ -16,6 +16,9 @@ pub enum ContractError {
     #[error("There is no enough tokens")]
     NotEnoughBalance {},
 
+    #[error("No ownership transfer proposed")]
+    NoPendingOwner {},
+
     #[error("Not correct Milestone status : {aust_balance} {estimate_exchange_rate} {epoch_exchange_rate} {withdraw_amount} {release_amount}")]
     Testing{
         aust_balance: String,
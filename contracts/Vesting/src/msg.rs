@@ -11,9 +11,11 @@ pub struct InstantiateMsg {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    SetConfig {
-        admin: String,
+    ProposeOwner {
+        owner: String,
     },
+    AcceptOwnership {},
+    CancelOwnershipProposal {},
     AddProject {
         project_id: Uint128,
         admin: String, 
@@ -64,7 +66,8 @@ pub enum QueryMsg {
     GetBalance { project_id: Uint128, wallet: String },
     GetProjectInfo { project_id: Uint128 },
     GetAllProjectInfo {},
-    GetOwner{ }
+    GetOwner{ },
+    GetPendingOwner{ }
 }
 
 //------------Config---------------------------------------
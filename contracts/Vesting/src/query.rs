@@ -9,7 +9,7 @@ use cosmwasm_std::{
 use cw20::{ Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse, TokenInfoResponse };
 
 use crate::msg::{QueryMsg, Config, ProjectInfo, UserInfo};
-use crate::state::{PROJECT_INFOS, OWNER};
+use crate::state::{PROJECT_INFOS, OWNER, PENDING_OWNER};
 use crate::contract::{ calc_pending };
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -35,6 +35,9 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             to_binary(&owner)
         }
 
+        QueryMsg::GetPendingOwner{ } =>
+            to_binary(&PENDING_OWNER.may_load(deps.storage)?),
+
         QueryMsg::GetUserInfo{ project_id, wallet } =>
             to_binary(&query_getuserinfo(deps, project_id, wallet)?),
     }
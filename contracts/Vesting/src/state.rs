@@ -5,5 +5,6 @@ use cw_storage_plus::{Item, Map, U128Key};
 use crate::msg::{ProjectInfo};
 
 pub const OWNER: Item<Addr> = Item::new("owner");
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
 
 pub const PROJECT_INFOS:Map<U128Key, ProjectInfo> = Map::new("project_infos");
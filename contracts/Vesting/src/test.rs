@@ -56,3 +56,40 @@ fn workflow(){
     println!("Project Info {:?}", res );
 }
 
+
+#[test]
+fn ownership_handover(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+//only owner proposes
+    let msg = ExecuteMsg::ProposeOwner{ owner: String::from("newowner") };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let res:Option<Addr> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetPendingOwner{}).unwrap()).unwrap();
+    assert_eq!(res, Some(Addr::unchecked("newowner")));
+//only proposed wallet accepts
+    let msg = ExecuteMsg::AcceptOwnership{};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("newowner", &[]), msg.clone()).unwrap();
+    let res:Addr = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetOwner{}).unwrap()).unwrap();
+    assert_eq!(res, Addr::unchecked("newowner"));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("newowner", &[]), msg);
+    assert!(res.is_err()); //proposal consumed
+//old owner lost rights, cancel needs a proposal
+    let msg = ExecuteMsg::ProposeOwner{ owner: String::from("admin") };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg.clone());
+    assert!(res.is_err());
+    let cancel = ExecuteMsg::CancelOwnershipProposal{};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("newowner", &[]), cancel.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("newowner", &[]), msg).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("newowner", &[]), cancel).unwrap();
+    let res:Option<Addr> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetPendingOwner{}).unwrap()).unwrap();
+    assert_eq!(res, None);
+}
@@ -0,0 +1,117 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use cosmwasm_std::{Addr, BankMsg, Coin, CosmosMsg, Decimal, Fraction, StdResult, to_binary, Uint128, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+//------------native coin or cw20 token a project accepts-----------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetInfo{
+    Native{ denom: String },
+    Cw20{ contract_addr: Addr },
+}
+
+impl fmt::Display for AssetInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AssetInfo::Native{ denom } => write!(f, "{}", denom),
+            AssetInfo::Cw20{ contract_addr } => write!(f, "{}", contract_addr),
+        }
+    }
+}
+
+impl AssetInfo {
+    //------stable denom parked with yield strategies-----------
+    pub fn uusd() -> Self {
+        AssetInfo::Native{ denom: "uusd".to_string() }
+    }
+
+    pub fn is_uusd(&self) -> bool {
+        self == &AssetInfo::uusd()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Asset{
+    pub info: AssetInfo,
+    pub amount: Uint128,
+}
+
+impl Asset {
+    //------message sending this asset from the contract to `recipient`-----
+    pub fn transfer_msg(&self, recipient: &Addr) -> StdResult<CosmosMsg> {
+        match &self.info {
+            AssetInfo::Native{ denom } => Ok(CosmosMsg::Bank(BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: vec![Coin::new(self.amount.u128(), denom.clone())],
+            })),
+            AssetInfo::Cw20{ contract_addr } => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: self.amount,
+                })?,
+                funds: Vec::new(),
+            })),
+        }
+    }
+}
+
+//------------asset owner lets projects accept, valued in uusd at its rate----------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AssetRate{
+    pub info: AssetInfo,
+    pub decimals: u8,
+    pub rate: Decimal, //UST per whole token
+}
+
+const UUSD_UNIT: u128 = 1_000_000;
+
+impl AssetRate {
+    //------uusd itself, always accepted------------
+    pub fn uusd() -> Self {
+        AssetRate{ info: AssetInfo::uusd(), decimals: 6, rate: Decimal::one() }
+    }
+
+    //------uusd value of `amount` base units, rounded down--------
+    pub fn to_uusd(&self, amount: Uint128) -> Uint128 {
+        amount.multiply_ratio(
+            UUSD_UNIT * self.rate.numerator(),
+            10u128.pow(self.decimals as u32) * self.rate.denominator()
+        )
+    }
+
+    //------base units worth `value` uusd, rounded down--------
+    pub fn from_uusd(&self, value: Uint128) -> Uint128 {
+        value.multiply_ratio(
+            10u128.pow(self.decimals as u32) * self.rate.denominator(),
+            UUSD_UNIT * self.rate.numerator()
+        )
+    }
+}
+
+//------------amount of `info` in per asset totals--------------------------
+pub fn asset_amount(assets: &[Asset], info: &AssetInfo) -> Uint128
+{
+    assets.iter()
+        .find(|asset| &asset.info == info)
+        .map(|asset| asset.amount)
+        .unwrap_or_default()
+}
+
+pub fn add_asset(assets: &mut Vec<Asset>, asset: &Asset)
+{
+    match assets.iter_mut().find(|x| x.info == asset.info) {
+        Some(x) => x.amount += asset.amount,
+        None => assets.push(asset.clone()),
+    }
+}
+
+pub fn sub_asset(assets: &mut [Asset], asset: &Asset)
+{
+    if let Some(x) = assets.iter_mut().find(|x| x.info == asset.info) {
+        x.amount = x.amount.saturating_sub(asset.amount);
+    }
+}
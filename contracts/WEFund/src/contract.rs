@@ -1,28 +1,40 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    Addr, to_binary, DepsMut, Env, MessageInfo, Response, StdResult,
+    Addr, to_binary, from_binary, DepsMut, Env, MessageInfo, Response,
     Uint128, CosmosMsg, BankMsg, QueryRequest, BankQuery, WasmMsg,
-    Coin, AllBalanceResponse
+    Coin, AllBalanceResponse, Storage, Event, StdResult, StdError
 };
-use cw2::set_contract_version;
+use cw2::{get_contract_version, set_contract_version};
 use cw_storage_plus::{U128Key};
-use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse, TokenInfoResponse};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, TokenInfoResponse,
+    BalanceResponse as Cw20BalanceResponse};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg};
-use crate::state::{Config, CONFIG, PROJECTSTATES, ProjectState, BackerState, VestingParameter,
-        PROJECT_SEQ, COMMUNITY, Milestone, Vote, save_projectstate, TeamMember, ProjectStatus};
-
-use crate::market::{ExecuteMsg as AnchorMarket, Cw20HookMsg,
-    QueryMsg as AnchorQuery, EpochStateResponse};                    
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, Cw20HookMsg, MetadataMsg, ReviewDecision,
+    ProjectParams, BackParams};
+use crate::state::{Config, CONFIG, ACCRUED_FEES, projects, ProjectState, BackerState,
+        PROJECT_SEQ, COMMUNITY, Vote, save_projectstate, ProjectStatus,
+        YieldStrategy, MilestoneVoting, DEFAULT_QUORUM, DEFAULT_THRESHOLD, DEFAULT_VOTE_PERIOD,
+        DEFAULT_MAX_REJECTIONS, ROLES, Role, PendingConfig, PENDING_CONFIG, PENDING_OWNER,
+        DEFAULT_TIMELOCK, FeeConfig, FEE_CONFIG, backers, load_backers, MILESTONES, load_milestone,
+        save_milestone, TEAMMEMBERS, Contribution, CONTRIBUTIONS, reviews, Review, ReviewStatus,
+        ReviewComment, SUBMIT_DEPOSIT, DEFAULT_SUBMIT_DEPOSIT, ListingVote, ListingVoting,
+        LISTING_VOTES, LISTING_VOTING, WeightSource, StatusTransition, PROJECT_HISTORY,
+        FundraisingRound, active_round};
+use crate::yield_adapter::{UST, get_adapter, project_strategy, validate_strategy};
+use crate::util::{check_onlyowner, check_role};
+use crate::asset::{Asset, AssetInfo, AssetRate, add_asset, sub_asset, asset_amount};
+use crate::fee::{BPS, calc_capped_fee, load_fee_config, validate_fee_config};
+use crate::metadata::{validate_metadata_msg, changed_fields};
+use crate::legacy::{LEGACY_CONFIG, migrate_config, migrate_projects};
+use crate::vote_weight::get_weight_source;
 
 use Vesting::msg::{ExecuteMsg as VestingMsg, VestingParameter as VestingParam};
 
 // version info for migration info
 const CONTRACT_NAME: &str = "WEFUND";
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
-const UST: u128 = 1000000; //ust unit
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -43,36 +55,88 @@ pub fn instantiate(
         .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
         .unwrap_or(info.sender.clone());
 
-    let anchor_market = msg
-        .anchor_market
-        .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
-        .unwrap_or(Addr::unchecked(
-            // String::from("terra1sepfj7s0aeg5967uxnfk4thzlerrsktkpelm5s")));//main net
-            String::from("terra15dwd5mj8v59wpj0wvt233mf5efdff808c5tkal")));//test net
-    let aust_token = msg
-        .aust_token
-        .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
-        .unwrap_or(Addr::unchecked(
-            // String::from("terra1hzh9vpxhsk8253se0vv5jj6etdvxu3nv8z07zu")));//main net
-            String::from("terra1ajt556dpzvjwl0kl5tzku3fc3p3knkg9mkv8jl")));//test net
+    let yield_strategy = match msg.yield_strategy{
+        Some(strategy) => validate_strategy(deps.api, strategy)?,
+        None => YieldStrategy::Hold
+    };
 
     let vesting_contract = msg
         .vesting_contract
         .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
         .unwrap_or(Addr::unchecked("".to_string()));
 
+    let timelock = msg.timelock.unwrap_or(Uint128::new(DEFAULT_TIMELOCK));
+
     let config = Config {
-        owner, wefund, anchor_market, aust_token, vesting_contract
+        owner, wefund, yield_strategy, vesting_contract, timelock,
+        accepted_assets: Vec::new(),
     };
 
     CONFIG.save(deps.storage, &config)?;
     PROJECT_SEQ.save(deps.storage, &Uint128::new(0))?;
     COMMUNITY.save(deps.storage, &Vec::new())?;
+    FEE_CONFIG.save(deps.storage, &FeeConfig::default())?;
 
     Ok(Response::new()
         .add_attribute("method", "instantiate"))
 }
 
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    //-----------only from older WEFUND, nothing to do once migrated-----------
+    let version = get_contract_version(deps.storage)?;
+    if version.contract != CONTRACT_NAME {
+        return Err(ContractError::CannotMigrate{ contract: version.contract });
+    }
+    if version.version == CONTRACT_VERSION {
+        return Ok(Response::new()
+            .add_attribute("method", "migrate")
+            .add_attribute("projects", "0"));
+    }
+
+    //-----------old escrow is contract's aust------
+    let legacy = LEGACY_CONFIG.load(deps.storage)?;
+    let aust_balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
+        legacy.aust_token.to_string(),
+        &Cw20QueryMsg::Balance{ address: _env.contract.address.to_string() }
+    )?;
+
+    //-----------config, then backers, milestones and team out of project state-----------
+    let config = migrate_config(deps.storage)?;
+    let migrated = migrate_projects(deps.storage, &config, aust_balance.balance)?;
+
+    //-----------uusd is retained fees, less community refunds old fail left behind------
+    let held: Uint128 = migrated.iter()
+        .map(|x| asset_amount(&x.held_assets, &AssetInfo::uusd()))
+        .sum();
+    let fees = deps.querier.query_balance(_env.contract.address.to_string(), "uusd")?
+        .amount.saturating_sub(held);
+    if !fees.is_zero() {
+        ACCRUED_FEES.save(deps.storage, &vec![Asset{ info: AssetInfo::uusd(), amount: fees }])?;
+    }
+
+    //-----------old votes had no window, reopen the one in progress-----------
+    for x in migrated.iter() {
+        if x.project_status == ProjectStatus::Releasing 
+            && x.project_milestonestep < x.milestone_count
+            && load_milestone(deps.storage, x.project_id, x.project_milestonestep)?
+                .milestone_status == Uint128::zero()
+        {
+            open_milestonevote(deps.storage, x, &_env)?;
+        }
+        //-----------old listing was owner's call, put it to community vote-----------
+        if x.project_status == ProjectStatus::WefundVote {
+            open_listingvote(deps.storage, &_env, x.project_id)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new()
+        .add_attribute("method", "migrate")
+        .add_attribute("projects", migrated.len().to_string()))
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
     deps: DepsMut,
@@ -81,352 +145,1009 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::SetConfig{ admin, wefund, anchor_market, aust_token, vesting_contract } 
-            => try_setconfig(deps, _env, info, admin, wefund, anchor_market, aust_token, vesting_contract),
+        ExecuteMsg::SetConfig{ wefund, yield_strategy, vesting_contract, timelock } 
+            => try_setconfig(deps, _env, info, wefund, yield_strategy, vesting_contract, timelock),
+
+        ExecuteMsg::ExecuteConfig{ } 
+            => try_executeconfig(deps, _env, info),
+
+        ExecuteMsg::CancelConfig{ } 
+            => try_cancelconfig(deps, info),
+
+        ExecuteMsg::ProposeOwner{ owner } 
+            => try_proposeowner(deps, info, owner),
+
+        ExecuteMsg::AcceptOwnership{ } 
+            => try_acceptownership(deps, info),
+
+        ExecuteMsg::CancelOwnershipProposal{ } 
+            => try_cancelownershipproposal(deps, info),
         ExecuteMsg::AddProject { 
-            project_company,
-            project_title,
-            project_description,
-            project_ecosystem,
-            project_createddate,
-            project_saft,
-            project_logo,
-            project_whitepaper,
-            project_website,
-            project_email,
+            project_metadata,
             creator_wallet,
             project_collected,
             project_milestones,
             project_teammembers,
             vesting,
-            token_addr
+            token_addr,
+            fundraising_deadline,
+            milestone_voting,
+            accepted_assets
         } => 
-            try_addproject(deps, _env, info, 
-                project_company,
-                project_title,
-                project_description,
-                project_ecosystem,
-                project_createddate,
-                project_saft,
-                project_logo,
-                project_whitepaper,
-                project_website,
-                project_email,
-                creator_wallet,
+            try_addproject(deps, _env, info, creator_wallet, ProjectParams{
+                project_metadata,
                 project_collected,
                 project_milestones,
                 project_teammembers,
                 vesting,
-                token_addr
-            ),
-
-        ExecuteMsg::Back2Project { project_id, backer_wallet, fundraising_stage, token_amount, otherchain, otherchain_wallet} => 
-            try_back2project(deps, _env, info, project_id, backer_wallet, fundraising_stage, token_amount, otherchain, otherchain_wallet),
+                token_addr,
+                fundraising_deadline,
+                milestone_voting,
+                accepted_assets
+            }),
+
+        ExecuteMsg::Back2Project { project_id, backer_wallet, fundraising_stage, otherchain, otherchain_wallet} => 
+            try_back2project(deps, _env, info, BackParams{
+                project_id,
+                backer_wallet,
+                fundraising_stage,
+                otherchain,
+                otherchain_wallet,
+            }),
+
+        ExecuteMsg::Receive(cw20_msg) =>
+            try_receive(deps, _env, info, cw20_msg),
 
         ExecuteMsg::CompleteProject{ project_id } =>
-            try_completeproject(deps, _env, project_id ),
+            try_completeproject(deps, _env, info, project_id ),
 
         ExecuteMsg::FailProject{ project_id } =>
-            try_failproject(deps, _env, project_id),
+            try_failproject(deps, _env, info, project_id),
         
+        ExecuteMsg::UpdateProjectMetadata{ project_id, project_metadata } =>
+            try_updateprojectmetadata(deps, info, project_id, project_metadata),
+
         ExecuteMsg::RemoveProject{ project_id } =>
             try_removeproject(deps, info, project_id),
+
+        ExecuteMsg::SubmitProject { 
+            project_metadata,
+            project_collected,
+            project_milestones,
+            project_teammembers,
+            vesting,
+            token_addr,
+            fundraising_deadline,
+            milestone_voting,
+            accepted_assets
+        } => 
+            try_submitproject(deps, _env, info, ProjectParams{
+                project_metadata,
+                project_collected,
+                project_milestones,
+                project_teammembers,
+                vesting,
+                token_addr,
+                fundraising_deadline,
+                milestone_voting,
+                accepted_assets
+            }),
+
+        ExecuteMsg::ReviewProject{ project_id, decision, comment, fee_bps } =>
+            try_reviewproject(deps, _env, info, project_id, decision, comment, fee_bps),
+
+        ExecuteMsg::ResubmitProject{ project_id, comment } =>
+            try_resubmitproject(deps, _env, info, project_id, comment),
+
+        ExecuteMsg::SetSubmitDeposit{ amount } =>
+            try_setsubmitdeposit(deps, info, amount),
+
+        ExecuteMsg::CastListingVote{ project_id, voted } =>
+            try_castlistingvote(deps, _env, info, project_id, voted),
+
+        ExecuteMsg::TallyListingVote{ project_id } =>
+            try_tallylistingvote(deps, _env, project_id),
+
+        ExecuteMsg::SetListingVoting{ listing_voting } =>
+            try_setlistingvoting(deps, info, listing_voting),
         
         ExecuteMsg::TransferAllCoins{wallet} =>
             try_transferallcoins(deps, _env, info, wallet),
 
         ExecuteMsg::AddCommunitymember{wallet} =>
-            try_addcommunitymember(deps, wallet),
+            try_addcommunitymember(deps, info, wallet),
 
         ExecuteMsg::RemoveCommunitymember{wallet} =>
-            try_removecommunitymember(deps, wallet),
+            try_removecommunitymember(deps, info, wallet),
 
-        ExecuteMsg::WefundApprove{project_id} =>
-            try_wefundapprove(deps, info, project_id),
+        ExecuteMsg::WefundApprove{project_id, fee_bps} =>
+            try_wefundapprove(deps, _env, info, project_id, fee_bps),
 
         ExecuteMsg::SetFundraisingStage{project_id, stage} =>
-            try_setfundraisingstage(deps, project_id, stage),
+            try_setfundraisingstage(deps, info, project_id, stage),
+
+        ExecuteMsg::SetFundraisingRounds{project_id, rounds} =>
+            try_setfundraisingrounds(deps, info, project_id, rounds),
+
+        ExecuteMsg::SetWalletLimits{project_id, min_amount, max_amount} =>
+            try_setwalletlimits(deps, info, project_id, min_amount, max_amount),
         
-        ExecuteMsg::SetMilestoneVote{project_id, wallet, voted} =>
-            try_setmilestonevote(deps, _env, info, project_id, wallet, voted),
+        ExecuteMsg::SetMilestoneVote{project_id, voted, on_behalf_of} =>
+            try_setmilestonevote(deps, _env, info, project_id, voted, on_behalf_of),
+
+        ExecuteMsg::DelegateVote{project_id, delegate} =>
+            try_delegatevote(deps, info, project_id, delegate),
+
+        ExecuteMsg::TallyMilestone{project_id} =>
+            try_tallymilestone(deps, _env, project_id),
+
+        ExecuteMsg::ResubmitMilestone{project_id, description, evidence} =>
+            try_resubmitmilestone(deps, _env, info, project_id, description, evidence),
 
         ExecuteMsg::ReleaseMilestone{project_id} =>
             try_releasemilestone(deps, _env, project_id),
 
         ExecuteMsg::SetProjectStatus{project_id, status} =>
-            try_setprojectstatus(deps, info, project_id, status),
+            try_setprojectstatus(deps, _env, info, project_id, status),
+
+        ExecuteMsg::SetProjectYieldStrategy{project_id, yield_strategy} =>
+            try_setprojectyieldstrategy(deps, info, project_id, yield_strategy),
+
+        ExecuteMsg::ExpireProject{project_id} =>
+            try_expireproject(deps, _env, project_id),
+
+        ExecuteMsg::ClaimRefund{project_id} =>
+            try_claimrefund(deps, info, project_id),
+
+        ExecuteMsg::GrantRole{wallet, role} =>
+            try_grantrole(deps, info, wallet, role),
+
+        ExecuteMsg::RevokeRole{wallet, role} =>
+            try_revokerole(deps, info, wallet, role),
+
+        ExecuteMsg::SetFeeConfig{fee_config} =>
+            try_setfeeconfig(deps, info, fee_config),
+
+        ExecuteMsg::SetAcceptedAssets{assets} =>
+            try_setacceptedassets(deps, info, assets),
     }
 }
-pub fn try_setprojectstatus(deps: DepsMut, info: MessageInfo, project_id: Uint128, status: Uint128)
+pub fn try_expireproject(deps: DepsMut, env: Env, project_id: Uint128)
     ->Result<Response, ContractError>
 {
-    //-----------check owner--------------------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    if info.sender != config.owner {
-        return Err(ContractError::Unauthorized{});
+    let x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
+
+    //-------check project status-------------------
+    if x.project_status != ProjectStatus::Fundraising { //only fundraising status
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
-//    let x:ProjectState = PROJECTSTATES.load(deps.storage, _project_id.u128().into())?;
-    //-------update-------------------------
-    PROJECTSTATES.update(deps.storage, project_id.u128().into(), |op| match op {
+
+    //-------anyone can fail it once deadline passed------------
+    let now = Uint128::from(env.block.time.seconds());
+    match x.fundraising_deadline {
+        Some(deadline) if now > deadline => {},
+        _ => return Err(ContractError::NotExpired{}),
+    }
+
+    fail_project(deps.storage, &env, x)?;
+
+    Ok(Response::new()
+    .add_attribute("action", "Expire project")
+    .add_attribute("project_id", project_id))
+}
+pub fn try_claimrefund(deps: DepsMut, info: MessageInfo, project_id: Uint128)
+    ->Result<Response, ContractError>
+{
+    let mut x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
+
+    //-------check project status-------------------
+    if x.project_status != ProjectStatus::Fail { //only failed project
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
+    }
+
+    //-------escrow shares and held assets recorded for sender when project failed------------
+    let key = (U128Key::new(project_id.u128()), info.sender.clone());
+    let mut backer = match backers().may_load(deps.storage, key.clone())? {
+        Some(backer) if !backer.refunded => backer,
+        _ => return Err(ContractError::NothingToRefund{}),
+    };
+    //-------no more than escrow holds now, rest stays claimable------------
+    let shares = std::cmp::min(backer.refund_shares, x.escrow_shares);
+    let mut assets = backer.refund_assets.clone();
+    assets.retain(|asset| asset.amount > Uint128::zero());
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut amount = Uint128::zero();
+    if shares > Uint128::zero() {
+        let config = CONFIG.load(deps.storage)?;
+        let withdrawal = get_adapter(&project_strategy(&config, &x))
+            .withdraw_shares(&deps.querier, shares)?;
+        amount = withdrawal.amount;
+        if amount > Uint128::zero() {
+            x.escrow_principal -= x.escrow_principal.multiply_ratio(shares, x.escrow_shares);
+            x.escrow_shares -= shares;
+            backer.refund_shares -= shares;
+
+            msgs.extend(withdrawal.msgs);
+            msgs.push(CosmosMsg::Bank(BankMsg::Send { 
+                to_address: info.sender.to_string(),
+                amount: vec![Coin::new(amount.u128(), "uusd")] 
+            }));
+        }
+    }
+    if amount == Uint128::zero() && assets.is_empty() {
+        return Err(ContractError::NothingToRefund{});
+    }
+    backer.refund_assets = Vec::new();
+    backer.refunded = backer.refund_shares == Uint128::zero();
+
+    //-------held assets go back as they were backed------------
+    for asset in assets.iter() {
+        sub_asset(&mut x.held_assets, asset);
+        msgs.push(asset.transfer_msg(&info.sender)?);
+    }
+
+    backers().save(deps.storage, key, &backer)?;
+    save_escrow(deps.storage, &x)?;
+
+    Ok(Response::new()
+    .add_messages(msgs)
+    .add_attribute("action", "Claim refund")
+    .add_attribute("amount", amount))
+}
+pub fn try_grantrole(deps: DepsMut, info: MessageInfo, wallet: String, role: Role)
+    ->Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
+
+    let wallet = deps.api.addr_validate(&wallet)?;
+    ROLES.update(deps.storage, wallet.clone(), |roles| -> Result<_, ContractError> {
+        let mut roles = roles.unwrap_or_default();
+        if !roles.contains(&role) {
+            roles.push(role.clone());
+        }
+        Ok(roles)
+    })?;
+
+    Ok(Response::new()
+    .add_attribute("action", "Grant role")
+    .add_attribute("wallet", wallet)
+    .add_attribute("role", format!("{:?}", role)))
+}
+pub fn try_revokerole(deps: DepsMut, info: MessageInfo, wallet: String, role: Role)
+    ->Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
+
+    let wallet = deps.api.addr_validate(&wallet)?;
+    let mut roles = ROLES.may_load(deps.storage, wallet.clone())?.unwrap_or_default();
+    roles.retain(|x| x != &role);
+    if roles.is_empty() {
+        ROLES.remove(deps.storage, wallet.clone());
+    } else {
+        ROLES.save(deps.storage, wallet.clone(), &roles)?;
+    }
+
+    Ok(Response::new()
+    .add_attribute("action", "Revoke role")
+    .add_attribute("wallet", wallet)
+    .add_attribute("role", format!("{:?}", role)))
+}
+pub fn try_setfeeconfig(deps: DepsMut, info: MessageInfo, fee_config: FeeConfig)
+    -> Result<Response, ContractError>
+{
+    check_role(deps.storage, info.sender, Role::Admin)?;
+
+    let fee_config = validate_fee_config(deps.api, fee_config)?;
+    FEE_CONFIG.save(deps.storage, &fee_config)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "Set fee config")
+        .add_attribute("fee_bps", fee_config.fee_bps))
+}
+pub fn try_setacceptedassets(deps: DepsMut, info: MessageInfo, assets: Vec<AssetRate>)
+    -> Result<Response, ContractError>
+{
+    check_onlyowner(deps.storage, info.sender)?;
+
+    //-------uusd is always accepted at par, others need a rate----------
+    let mut accepted_assets: Vec<AssetRate> = Vec::new();
+    for asset in assets {
+        if asset.info.is_uusd() || asset.rate.is_zero() || asset.decimals > 18
+            || accepted_assets.iter().any(|x| x.info == asset.info)
+        {
+            return Err(ContractError::InvalidAssetRate{ asset: asset.info.to_string() });
+        }
+        let info = match asset.info {
+            AssetInfo::Cw20{ contract_addr } => AssetInfo::Cw20{
+                contract_addr: deps.api.addr_validate(contract_addr.as_str())?
+            },
+            native => native,
+        };
+        accepted_assets.push(AssetRate{ info, ..asset });
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.accepted_assets = accepted_assets;
+        Ok(config)
+    })?;
+    Ok(Response::new()
+        .add_attribute("action", "Set accepted assets"))
+}
+pub fn try_setprojectyieldstrategy(deps: DepsMut, info: MessageInfo, project_id: Uint128, 
+    yield_strategy: Option<YieldStrategy>)
+    ->Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::TreasuryOperator)?;
+
+    let yield_strategy = match yield_strategy{
+        Some(strategy) => Some(validate_strategy(deps.api, strategy)?),
+        None => None
+    };
+
+    projects().update(deps.storage, project_id.u128().into(), |op| match op {
         None => Err(ContractError::NotRegisteredProject {}),
         Some(mut project) => {
-            if status == Uint128::zero() {
-                project.project_status = ProjectStatus::WefundVote;
-            } else if status == Uint128::from(1u64){
-                project.project_status = ProjectStatus::Fundraising;
-            } else if status == Uint128::from(2u64){
-                project.project_status = ProjectStatus::Releasing;
-            } else if status == Uint128::from(3u64){
-                project.project_status = ProjectStatus::Done;
-            } else if status == Uint128::from(4u64){
-                project.project_status = ProjectStatus::Fail;
+            //------funds already parked can not follow a switch-------------
+            if project.backerbacked_amount + project.communitybacked_amount != Uint128::zero() {
+                return Err(ContractError::AlreadyBacked{});
             }
+            project.yield_strategy = yield_strategy;
             Ok(project)
         }
     })?;
+
+    Ok(Response::new()
+    .add_attribute("action", "Set project yield strategy"))
+}
+pub fn try_setprojectstatus(deps: DepsMut, env: Env, info: MessageInfo, project_id: Uint128,
+    status: ProjectStatus)
+    ->Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
+
+    let mut x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
+    let from = x.project_status.clone();
+
+    //-------listing goes through review and approve like WefundApprove------
+    if from == ProjectStatus::WefundVote && status == ProjectStatus::Fundraising {
+        return try_wefundapprove(deps, env, info, project_id, None);
+    }
+    //-------done only once escrow is paid out to creator-------------
+    if status == ProjectStatus::Done {
+        x.held_assets.retain(|asset| asset.amount > Uint128::zero());
+        if x.escrow_shares > Uint128::zero() || x.escrow_principal > Uint128::zero()
+            || !x.held_assets.is_empty()
+        {
+            return Err(ContractError::EscrowNotEmpty{});
+        }
+    }
+
+    //-------failed project records refunds of its backers-------------
+    if status == ProjectStatus::Fail {
+        fail_project(deps.storage, &env, x)?;
+    } else {
+        transition_status(deps.storage, &env, &mut x, status)?;
+        projects().update(deps.storage, project_id.u128().into(), |op| match op {
+            None => Err(ContractError::NotRegisteredProject {}),
+            Some(mut project) => {
+                project.project_status = x.project_status.clone();
+                Ok(project)
+            }
+        })?;
+        //-------releasing starts with vote on first milestone-------
+        if x.project_status == ProjectStatus::Releasing {
+            open_milestonevote(deps.storage, &x, &env)?;
+        }
+    }
+
     Ok(Response::new()
-    .add_attribute("action", "Set project status"))
+    .add_attribute("action", "Set project status")
+    .add_attribute("from", format!("{:?}", from)))
 }
-pub fn convert_str_int(str: String)
-    ->u128
+//------------move project along transition table, recorded in its history----------
+pub fn transition_status(store: &mut dyn Storage, env: &Env, x: &mut ProjectState, to: ProjectStatus)
+    -> Result<(), ContractError>
 {
-    let bytes = str.into_bytes();
-    let mut res: u128 = 0;
-    let mut dot = false;
-    let mut dotbelow = 0;
-
-    for i in 0..bytes.len(){
-        if bytes[i] < 48{
-            dot = true;
+    if !x.project_status.can_transition(&to) {
+        return Err(ContractError::InvalidTransition{ from: x.project_status.clone(), to });
+    }
+
+    PROJECT_HISTORY.update(store, x.project_id.u128().into(), |history| -> StdResult<_> {
+        let mut history = history.unwrap_or_default();
+        history.push(StatusTransition{
+            from: x.project_status.clone(),
+            to: to.clone(),
+            timestamp: Uint128::from(env.block.time.seconds()),
+        });
+        Ok(history)
+    })?;
+    x.project_status = to;
+    Ok(())
+}
+//---------write back escrow counters of a project-------------------------
+pub fn save_escrow(store: &mut dyn Storage, x: &ProjectState)
+    -> Result<ProjectState, ContractError>
+{
+    projects().update(store, x.project_id.u128().into(), |op| match op {
+        None => Err(ContractError::NotRegisteredProject {}),
+        Some(mut project) => {
+            project.escrow_principal = x.escrow_principal;
+            project.escrow_shares = x.escrow_shares;
+            project.held_assets = x.held_assets.clone();
+            Ok(project)
         }
-        else if dotbelow < 6 {
-            res = res * 10 + (bytes[i] - 48) as u128;
-            if dot {
-                dotbelow += 1;
+    })
+}
+//------------split escrow left among backers not refunded yet, per backed asset----------
+pub fn record_refunds(store: &mut dyn Storage, x: &ProjectState)
+    -> Result<(), ContractError>
+{
+    let mut backer_states = load_backers(store, x.project_id)?;
+    backer_states.retain(|backer| !backer.refunded);
+
+    let mut outstanding: Vec<Asset> = Vec::new();
+    for backer in backer_states.iter() {
+        for asset in backer.backed_assets.iter() {
+            add_asset(&mut outstanding, asset);
+        }
+    }
+    for mut backer in backer_states {
+        //------uusd backers share escrow shares, others the asset held------
+        backer.refund_shares = Uint128::zero();
+        backer.refund_assets = Vec::new();
+        for asset in backer.backed_assets.iter() {
+            let total = asset_amount(&outstanding, &asset.info);
+            if total == Uint128::zero() {
+                continue;
+            }
+            if asset.info.is_uusd() {
+                backer.refund_shares = x.escrow_shares.multiply_ratio(asset.amount, total);
+            } else {
+                let held = asset_amount(&x.held_assets, &asset.info);
+                add_asset(&mut backer.refund_assets, &Asset{
+                    info: asset.info.clone(),
+                    amount: held.multiply_ratio(asset.amount, total),
+                });
             }
         }
+        backers().save(store, (x.project_id.u128().into(), backer.backer_wallet.clone()), &backer)?;
     }
-    return res;
+    Ok(())
+}
+//------------set project FAIL, backers claim their refund later------------
+pub fn fail_project(store: &mut dyn Storage, env: &Env, mut x: ProjectState)
+    -> Result<ProjectState, ContractError>
+{
+    transition_status(store, env, &mut x, ProjectStatus::Fail)?;
+    record_refunds(store, &x)?;
+
+    projects().update(store, x.project_id.u128().into(), |op| match op {
+        None => Err(ContractError::NotRegisteredProject {}),
+        Some(mut project) => {
+            project.project_status = ProjectStatus::Fail;
+            Ok(project)
+        }
+    })
+}
+//------------open voting window of current milestone, weighted by backed ust-----
+pub fn open_milestonevote(store: &mut dyn Storage, x: &ProjectState, env: &Env)
+    -> Result<(), ContractError>
+{
+    if x.project_milestonestep >= x.milestone_count {
+        return Ok(());
+    }
+
+    let mut milestone_votes: Vec<Vote> = Vec::new();
+    for backer in load_backers(store, x.project_id)? {
+        milestone_votes.push(Vote{
+            wallet: backer.backer_wallet,
+            voted: None,
+            weight: backer.ust_amount.amount,
+            delegate: None,
+        });
+    }
+
+    let now = Uint128::from(env.block.time.seconds());
+    let mut milestone = load_milestone(store, x.project_id, x.project_milestonestep)?;
+    milestone.milestone_status = Uint128::zero(); //voting
+    milestone.milestone_votes = milestone_votes;
+    milestone.milestone_votestart = now;
+    milestone.milestone_voteend = now + x.milestone_voting.vote_period;
+    save_milestone(store, x.project_id, x.project_milestonestep, &milestone)?;
+    Ok(())
 }
 pub fn try_releasemilestone(deps: DepsMut, _env: Env, _project_id: Uint128 ) 
     -> Result<Response, ContractError>
 {
     //--------Get project info----------------------------
-    let x:ProjectState = PROJECTSTATES.load(deps.storage, _project_id.u128().into())?;
+    let mut x:ProjectState = projects().load(deps.storage, _project_id.u128().into())?;
 
     //--------Checking project status-------------------------
     if x.project_status != ProjectStatus::Releasing{//only releasing status
-        return Err(ContractError::NotCorrectStatus{status: x.project_status as u32});
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
 
-    //---------get hope to release amount---------------------------
-    let step = x.project_milestonestep.u128() as usize;
-    let release_amount = 
-        x.milestone_states[step].milestone_amount.u128() * UST;
-
-    //---------calc total deposited to anchor----------------
-    //----------map to vec-----------------------
-    let all: StdResult<Vec<_>> = PROJECTSTATES.range(deps.storage, None, None, 
-        cosmwasm_std::Order::Ascending).collect();
-    let all = all.unwrap();
+    //---------only milestone passed by tally---------------------------
+    let step = x.project_milestonestep;
+    let mut milestone = load_milestone(deps.storage, x.project_id, step)?;
+    if milestone.milestone_status != Uint128::new(1){//only releasing status
+        return Err(ContractError::NotCorrectMilestoneStatus{
+            step:step.u128() as usize, status:milestone.milestone_status 
+        })
+    }
 
-    let mut total_deposited = 0;
-    for x in all{
-        let prj = x.1;
-        total_deposited += prj.communitybacked_amount.u128() + prj.backerbacked_amount.u128();
+    //---------get hope to release amount, split by share of each backed asset-----
+    let milestone_amount = 
+        milestone.milestone_amount.u128() * UST;
+    let total_backed: Uint128 = x.backed_assets.iter().map(|asset| asset.amount).sum();
+    let release_amount = if total_backed == Uint128::zero() {
+        milestone_amount
+    } else {
+        Uint128::new(milestone_amount)
+            .multiply_ratio(asset_amount(&x.backed_assets, &AssetInfo::uusd()), total_backed)
+            .u128()
+    };
 
-        for i in 0..(prj.project_milestonestep.u128() as usize){
-            total_deposited -= prj.milestone_states[i].milestone_amount.u128() * UST;
+    //---------last milestone pays out whatever is left, yield included---------
+    let last = step + Uint128::new(1) >= x.milestone_count;
+
+    let mut held_release: Vec<CosmosMsg> = Vec::new();
+    if total_backed > Uint128::zero() {
+        for held in x.held_assets.iter_mut() {
+            let share = Uint128::new(milestone_amount)
+                .multiply_ratio(asset_amount(&x.backed_assets, &held.info), total_backed);
+            let amount = if last { held.amount } else { std::cmp::min(share, held.amount) };
+            if amount == Uint128::zero() {
+                continue;
+            }
+            held.amount -= amount;
+            held_release.push(Asset{ info: held.info.clone(), amount }
+                .transfer_msg(&x.creator_wallet)?);
         }
     }
-    //----------load config and read aust token address-----------------
+    x.held_assets.retain(|asset| asset.amount > Uint128::zero());
+
+    //----------load config and pick project yield strategy-----------------
     let config = CONFIG.load(deps.storage).unwrap();
-    
-    //--------get aust balance---------------------
-    let aust_balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
-        config.aust_token.clone(),
-        &Cw20QueryMsg::Balance{
-            address: _env.contract.address.to_string(),
-        }
-    )?;
+    let strategy = project_strategy(&config, &x);
+
+    //----------redeem only this project's shares for release amount---------
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut exchange_rate: u128 = 0;
+    if (release_amount > 0 || last) && x.escrow_shares > Uint128::zero() {
+        let withdrawal = if last {
+            get_adapter(&strategy).withdraw_shares(&deps.querier, x.escrow_shares)?
+        } else {
+            get_adapter(&strategy).withdraw(&deps.querier, release_amount, x.escrow_shares)?
+        };
 
-    //----------calc declaim aust amount---aust*(release/total)-----------
-    let mut estimate_exchange_rate = total_deposited * UST/aust_balance.balance.u128();
+        x.escrow_shares -= withdrawal.shares;
+        x.escrow_principal = if last {
+            Uint128::zero()
+        } else {
+            x.escrow_principal.saturating_sub(Uint128::new(release_amount))
+        };
+        exchange_rate = withdrawal.exchange_rate;
+
+        //---------send to creator wallet-------------
+        msgs.extend(withdrawal.msgs);
+        msgs.push(CosmosMsg::Bank(BankMsg::Send { 
+            to_address: x.creator_wallet.to_string(),
+            amount: vec![Coin::new(withdrawal.amount.u128(), "uusd")] 
+        }));
+    }
+    msgs.extend(held_release);
 
-    //--------get exchange rate between ust and aust ---------------------
-    let epoch: EpochStateResponse = deps.querier.query_wasm_smart(
-        config.anchor_market.to_string(),
-        &AnchorQuery::EpochState{
-            block_height: None,
-            distributed_interest: None,
-        }
-    )?;
-    let epoch_exchange_rate = convert_str_int(epoch.exchange_rate.to_string());
-    
-    if estimate_exchange_rate < epoch_exchange_rate{
-        estimate_exchange_rate = epoch_exchange_rate;
-    }
-
-    let withdraw_amount = release_amount * UST / estimate_exchange_rate;
-    let release_amount = withdraw_amount * epoch_exchange_rate / UST;
-
-    //----ask aust_token for transfer to anchor martket and execute redeem_stable ----------
-    let withdraw = WasmMsg::Execute {
-        contract_addr: String::from(config.aust_token),
-        msg: to_binary(&Cw20ExecuteMsg::Send {
-            contract: config.anchor_market.to_string(),
-            msg: to_binary(&Cw20HookMsg::RedeemStable{}).unwrap(), //redeem_stable{}
-            amount: Uint128::new(withdraw_amount)
-        }).unwrap(),
-        funds: Vec::new()
-    };
+    //---------switch to next milestone step---------------------
+    milestone.milestone_status = Uint128::new(2); //released
+    save_milestone(deps.storage, x.project_id, step, &milestone)?;
+    x.project_milestonestep += Uint128::new(1);
 
-    // return Err(ContractError::Testing{
-    //     aust_balance: aust_balance.balance.to_string(),
-    //     estimate_exchange_rate: estimate_exchange_rate.to_string(),
-    //     epoch_exchange_rate: epoch_exchange_rate.to_string(),
-    //     withdraw_amount: withdraw_amount.to_string(),
-    //     release_amount: release_amount.to_string()
-    // });
+    //-----------check milestone done---------------------
+    if last {
+        transition_status(deps.storage, &_env, &mut x, ProjectStatus::Done)?;
+    }
 
-    //---------send to creator wallet-------------
-    let ust_release = Coin::new(release_amount, "uusd");
-    let send2_creator = BankMsg::Send { 
-        to_address: x.creator_wallet.to_string(),
-        amount: vec![ust_release] 
-    };
+    //-------update-------------------------
+    projects().update(deps.storage, _project_id.u128().into(), |op| match op {
+        None => Err(ContractError::NotRegisteredProject {}),
+        Some(mut project) => {
+            project.escrow_principal = x.escrow_principal;
+            project.escrow_shares = x.escrow_shares;
+            project.held_assets = x.held_assets.clone();
+            project.project_milestonestep = x.project_milestonestep;
+            project.project_status = x.project_status.clone();
+            Ok(project)
+        }
+    })?;
+
+    //-----------vote on next milestone---------------------
+    if x.project_status == ProjectStatus::Releasing {
+        open_milestonevote(deps.storage, &x, &_env)?;
+    }
 
     Ok(Response::new()
-    .add_messages(vec![
-        CosmosMsg::Wasm(withdraw),
-        CosmosMsg::Bank(send2_creator)])
+    .add_messages(msgs)
     .add_attribute("action", "release milestone")
-    .add_attribute("epoch_exchange_rate", epoch.exchange_rate.to_string())
+    .add_attribute("exchange_rate", exchange_rate.to_string())
     )
 }
-pub fn try_setmilestonevote(deps: DepsMut, _env:Env, info:MessageInfo, project_id: Uint128, wallet: String, voted: bool)
+pub fn try_setmilestonevote(deps: DepsMut, _env:Env, info:MessageInfo, project_id: Uint128, voted: bool,
+    on_behalf_of: Option<String>)
     -> Result<Response, ContractError>
 {
-    let mut x:ProjectState = PROJECTSTATES.load(deps.storage, project_id.u128().into())?;
+    let x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
     
     //-------check project status-------------------
     if x.project_status != ProjectStatus::Releasing { //only releasing status
-        return Err(ContractError::NotCorrectStatus{status:x.project_status as u32});
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
 
-    let wallet = deps.api.addr_validate(&wallet).unwrap();
-    let step = x.project_milestonestep.u128() as usize;
+    //-------vote of sender, or of backer who delegated to sender-------------
+    let (wallet, delegate) = match on_behalf_of {
+        None => (info.sender.clone(), None),
+        Some(backer) => {
+            let backer = deps.api.addr_validate(&backer)?;
+            let delegated = backers().may_load(deps.storage, (project_id.u128().into(), backer.clone()))?
+                .is_some_and(|state| state.delegate == Some(info.sender.clone()));
+            if !delegated {
+                return Err(ContractError::Unauthorized{});
+            }
+            (backer, Some(info.sender.clone()))
+        }
+    };
+    let step = x.project_milestonestep;
+    let mut milestone = load_milestone(deps.storage, project_id, step)?;
 
-    if x.milestone_states[step].milestone_status != Uint128::zero(){//only voting status
+    if milestone.milestone_status != Uint128::zero(){//only voting status
         return Err(ContractError::NotCorrectMilestoneStatus{
-            step:step, status:x.milestone_states[step].milestone_status 
+            step:step.u128() as usize, status:milestone.milestone_status 
         })
     }
 
-    //------set vot status and check all voted for same backer--------------------
-    // let index = x.milestone_states[step].milestone_votes.iter().position(|x|x.wallet == wallet).unwrap();
-    // x.milestone_states[step].milestone_votes[index].voted = voted;
+    //-------check voting window-------------------
+    let now = Uint128::from(_env.block.time.seconds());
+    if now < milestone.milestone_votestart 
+        || now > milestone.milestone_voteend 
+    {
+        return Err(ContractError::VotingClosed{});
+    }
+
+    //------set vote of backer, tallied once window closes--------------------
+    match milestone.milestone_votes.iter_mut().find(|vote| vote.wallet == wallet) {
+        Some(vote) => {
+            vote.voted = Some(voted);
+            vote.delegate = delegate;
+        },
+        None => return Err(ContractError::NotBackerWallet{}),
+    }
 
-    let mut all_voted = true;
-    for vote in x.milestone_states[step].milestone_votes.iter_mut() {
-        if vote.wallet == wallet{
-            vote.voted = voted;
+    //-------update-------------------------
+    save_milestone(deps.storage, project_id, step, &milestone)?;
+
+    Ok(Response::new()
+    .add_attribute("action", "Set milestone vote")
+    )
+}
+pub fn try_delegatevote(deps: DepsMut, info:MessageInfo, project_id: Uint128, delegate: Option<String>)
+    -> Result<Response, ContractError>
+{
+    let delegate = match delegate {
+        Some(delegate) => Some(deps.api.addr_validate(&delegate)?),
+        None => None,
+    };
+
+    //-------set delegate on backing of sender-------------
+    backers().update(deps.storage, (project_id.u128().into(), info.sender.clone()), |op| match op {
+        None => Err(ContractError::NotBackerWallet{}),
+        Some(mut backer) => {
+            backer.delegate = delegate.clone();
+            Ok(backer)
         }
-        all_voted = all_voted & vote.voted;
+    })?;
+
+    Ok(Response::new()
+    .add_attribute("action", "Delegate vote")
+    .add_attribute("delegate", delegate.map(|d| d.to_string()).unwrap_or_default())
+    )
+}
+pub fn try_tallymilestone(deps: DepsMut, env:Env, project_id: Uint128)
+    -> Result<Response, ContractError>
+{
+    let x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
+
+    //-------check project status-------------------
+    if x.project_status != ProjectStatus::Releasing { //only releasing status
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
 
-    let mut deps = deps;
-    if all_voted{
-        x.milestone_states[step].milestone_status = Uint128::new(1); //switch to releasing status
-        //-----------------release function---------------
-        let res = execute(deps.branch(), _env, info, 
-                    ExecuteMsg::ReleaseMilestone{project_id});
+    let step = x.project_milestonestep;
+    let mut milestone = load_milestone(deps.storage, project_id, step)?;
+    if milestone.milestone_status != Uint128::zero(){//only voting status
+        return Err(ContractError::NotCorrectMilestoneStatus{
+            step:step.u128() as usize, status:milestone.milestone_status 
+        })
+    }
 
-        x.milestone_states[step].milestone_status = Uint128::new(2); //switch to released status
-        x.project_milestonestep += Uint128::new(1); //switch to next milestone step
-        
-        //-----------check milestone done---------------------
-        if x.project_milestonestep >= Uint128::new(x.milestone_states.len() as u128){
-            x.project_status = ProjectStatus::Done; //switch to project done status
+    //-------anyone can tally once window closed------------
+    let now = Uint128::from(env.block.time.seconds());
+    if now <= milestone.milestone_voteend {
+        return Err(ContractError::VotingNotEnded{});
+    }
+
+    //-------sum weight of backers, of cast votes and of yes votes-------------
+    let mut total_weight = Uint128::zero();
+    let mut cast_weight = Uint128::zero();
+    let mut yes_weight = Uint128::zero();
+    for vote in milestone.milestone_votes.iter() {
+        total_weight += vote.weight;
+        match vote.voted {
+            Some(true) => { cast_weight += vote.weight; yes_weight += vote.weight; },
+            Some(false) => cast_weight += vote.weight,
+            None => {},
         }
+    }
 
-        //-------update-------------------------
-        PROJECTSTATES.update(deps.storage, project_id.u128().into(), |op| match op {
-            None => Err(ContractError::NotRegisteredProject {}),
-            Some(mut project) => {
-                project.milestone_states = x.milestone_states;
-                project.project_milestonestep = x.project_milestonestep;
-                project.project_status = x.project_status;
-                Ok(project)
-            }
-        })?;
-        return res;
+    let voting = x.milestone_voting.clone();
+    let quorum_reached = cast_weight * Uint128::new(100) >= total_weight * voting.quorum;
+    let passed = quorum_reached && yes_weight > Uint128::zero()
+        && yes_weight * Uint128::new(100) >= cast_weight * voting.threshold;
+
+    if passed {
+        milestone.milestone_status = Uint128::new(1); //switch to releasing status
+    } else {
+        milestone.milestone_status = Uint128::new(3); //switch to rejected status
+        milestone.milestone_rejections += Uint128::new(1);
     }
+
     //-------update-------------------------
-    PROJECTSTATES.update(deps.storage, project_id.u128().into(), |op| match op {
+    save_milestone(deps.storage, project_id, step, &milestone)?;
+
+    if passed {
+        //-----------------release function---------------
+        let res = try_releasemilestone(deps, env, project_id)?;
+        return Ok(res.add_attribute("result", "passed"));
+    }
+
+    //-------too many rejections, fail project and refund escrow left-------------
+    if milestone.milestone_rejections > voting.max_rejections {
+        fail_project(deps.storage, &env, x)?;
+        return Ok(Response::new()
+            .add_attribute("action", "Tally milestone")
+            .add_attribute("result", "rejected")
+            .add_attribute("project_status", "fail"));
+    }
+
+    Ok(Response::new()
+    .add_attribute("action", "Tally milestone")
+    .add_attribute("result", "rejected")
+    )
+}
+pub fn try_resubmitmilestone(deps: DepsMut, env:Env, info:MessageInfo, project_id: Uint128,
+    description: String, evidence: Vec<String>)
+    -> Result<Response, ContractError>
+{
+    let x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
+
+    //-------only creator of project-------------------
+    if info.sender != x.creator_wallet {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    //-------check project status-------------------
+    if x.project_status != ProjectStatus::Releasing { //only releasing status
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
+    }
+
+    let step = x.project_milestonestep;
+    let mut milestone = load_milestone(deps.storage, project_id, step)?;
+    if milestone.milestone_status != Uint128::new(3){//only rejected status
+        return Err(ContractError::NotCorrectMilestoneStatus{
+            step:step.u128() as usize, status:milestone.milestone_status 
+        })
+    }
+
+    //-------update milestone and vote again on it-------------
+    milestone.milestone_description = description;
+    milestone.milestone_evidence = evidence;
+    save_milestone(deps.storage, project_id, step, &milestone)?;
+    open_milestonevote(deps.storage, &x, &env)?;
+
+    Ok(Response::new()
+    .add_attribute("action", "Resubmit milestone")
+    .add_attribute("step", step.to_string())
+    )
+}
+
+pub fn try_setfundraisingstage(deps: DepsMut, info:MessageInfo, project_id: Uint128, stage: Uint128)
+    -> Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::ProjectReviewer)?;
+
+    projects().update(deps.storage, project_id.u128().into(), |op| match op {
         None => Err(ContractError::NotRegisteredProject {}),
         Some(mut project) => {
-            project.milestone_states = x.milestone_states;
-            project.project_milestonestep = x.project_milestonestep;
-            project.project_status = x.project_status;
+            //-------stage is one of the rounds when project has rounds------------
+            if !project.fundraising_rounds.is_empty() 
+                && stage.u128() >= project.fundraising_rounds.len() as u128
+            {
+                return Err(ContractError::InvalidFundraisingRounds{});
+            }
+            project.fundraising_stage = stage;
             Ok(project)
         }
     })?;
 
     Ok(Response::new()
-    .add_attribute("action", "Set milestone vote")
+    .add_attribute("action", "Set Fundraising stage")
     )
 }
 
-pub fn try_setfundraisingstage(deps: DepsMut, project_id: Uint128, stage: Uint128)
+pub fn try_setfundraisingrounds(deps: DepsMut, info:MessageInfo, project_id: Uint128,
+    rounds: Vec<FundraisingRound>)
     -> Result<Response, ContractError>
 {
-    PROJECTSTATES.update(deps.storage, project_id.u128().into(), |op| match op {
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::ProjectReviewer)?;
+
+    //-----------one round per vesting stage, in order, not overlapping-----------
+    let mut last_end = Uint128::zero();
+    for round in rounds.iter() {
+        if round.start >= round.end || round.start < last_end || round.cap == Uint128::zero() {
+            return Err(ContractError::InvalidFundraisingRounds{});
+        }
+        last_end = round.end;
+    }
+
+    projects().update(deps.storage, project_id.u128().into(), |op| match op {
         None => Err(ContractError::NotRegisteredProject {}),
         Some(mut project) => {
-            project.fundraising_stage = stage;
+            if project.project_status != ProjectStatus::WefundVote
+                && project.project_status != ProjectStatus::Fundraising
+            {
+                return Err(ContractError::NotCorrectStatus{status: project.project_status});
+            }
+            //-------raised of rounds can't follow a change------------
+            if project.backerbacked_amount + project.communitybacked_amount != Uint128::zero() {
+                return Err(ContractError::AlreadyBacked{});
+            }
+            if !rounds.is_empty() && rounds.len() != project.vesting.len() {
+                return Err(ContractError::InvalidFundraisingRounds{});
+            }
+            project.fundraising_stage = Uint128::zero();
+            project.fundraising_rounds = rounds.iter().map(|round| FundraisingRound{
+                raised: Uint128::zero(),
+                ..round.clone()
+            }).collect();
             Ok(project)
         }
     })?;
 
     Ok(Response::new()
-    .add_attribute("action", "Set Fundraising stage")
+    .add_attribute("action", "Set Fundraising rounds")
+    .add_attribute("rounds", rounds.len().to_string())
     )
 }
 
-pub fn try_wefundapprove(deps: DepsMut, info:MessageInfo, project_id: Uint128)
-    ->Result<Response, ContractError>
+pub fn try_setwalletlimits(deps: DepsMut, info:MessageInfo, project_id: Uint128,
+    min_amount: Option<Uint128>, max_amount: Option<Uint128>)
+    -> Result<Response, ContractError>
 {
-    //-----------check owner--------------------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    if info.sender != config.owner{
-        return Err(ContractError::Unauthorized{});
-    }
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::ProjectReviewer)?;
 
-    let mut x:ProjectState = PROJECTSTATES.load(deps.storage, project_id.u128().into())?;
-    
-    //-------check project status-------------------
-    if x.project_status != ProjectStatus::WefundVote { //only wefund approve status
-        return Err(ContractError::NotCorrectStatus{status:x.project_status as u32});
+    if let (Some(min), Some(max)) = (min_amount, max_amount) {
+        if min > max {
+            return Err(ContractError::InvalidWalletLimits{});
+        }
     }
-    x.project_status = ProjectStatus::Fundraising; //switch to fundraising status
 
-    PROJECTSTATES.update(deps.storage, project_id.u128().into(), |op| match op {
+    projects().update(deps.storage, project_id.u128().into(), |op| match op {
         None => Err(ContractError::NotRegisteredProject {}),
         Some(mut project) => {
-            project.project_status = x.project_status;
+            //-------only before fundraising is over------------
+            if project.project_status != ProjectStatus::WefundVote
+                && project.project_status != ProjectStatus::Fundraising
+            {
+                return Err(ContractError::NotCorrectStatus{status: project.project_status});
+            }
+            project.wallet_min_amount = min_amount;
+            project.wallet_max_amount = max_amount;
             Ok(project)
         }
     })?;
 
     Ok(Response::new()
+    .add_attribute("action", "Set wallet limits")
+    )
+}
+
+pub fn try_wefundapprove(deps: DepsMut, env: Env, info:MessageInfo, project_id: Uint128,
+    fee_bps: Option<Uint128>)
+    ->Result<Response, ContractError>
+{
+    //-----------admin override of community listing vote--------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
+
+    //-------submitted projects are listed only after review-------------
+    if let Some(review) = reviews().may_load(deps.storage, project_id.u128().into())? {
+        if review.status != ReviewStatus::Approved {
+            return Err(ContractError::UnderReview{});
+        }
+    }
+
+    let msgs = approve_project(deps.storage, &env, project_id, fee_bps)?;
+    LISTING_VOTES.remove(deps.storage, project_id.u128().into());
+
+    Ok(Response::new()
+    .add_messages(msgs)
     .add_attribute("action", "Wefund Approve")
+    .add_attribute("fee_bps", fee_bps.map(|bps| bps.to_string()).unwrap_or_default())
     )
 }
+pub fn approve_project(store: &mut dyn Storage, env: &Env, project_id: Uint128, fee_bps: Option<Uint128>)
+    -> Result<Vec<CosmosMsg>, ContractError>
+{
+    let mut x:ProjectState = projects().load(store, project_id.u128().into())?;
+
+    //-------fee rate of this project instead of fee config one-----------
+    if let Some(bps) = fee_bps {
+        if bps > Uint128::new(BPS) {
+            return Err(ContractError::InvalidFeeConfig{});
+        }
+    }
+    transition_status(store, env, &mut x, ProjectStatus::Fundraising)?;
+
+    projects().update(store, project_id.u128().into(), |op| match op {
+        None => Err(ContractError::NotRegisteredProject {}),
+        Some(mut project) => {
+            project.project_status = x.project_status.clone();
+            project.fee_bps = fee_bps;
+            Ok(project)
+        }
+    })?;
+
+    //----------vesting contract learns of project once it is listed------------
+    let config = CONFIG.load(store)?;
+    if config.vesting_contract.as_str().is_empty() || x.token_addr.as_str().is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut vesting_params: Vec<VestingParam> = Vec::new();
+    for param in x.vesting.iter() {
+        vesting_params.push(VestingParam{
+            soon: param.stage_soon,
+            after: param.stage_after,
+            period: param.stage_period,
+        })
+    }
+    let add_vesting_project = WasmMsg::Execute {
+        contract_addr: config.vesting_contract.to_string(),
+        msg: to_binary(
+            &VestingMsg::AddProject {
+                project_id: x.project_id,
+                admin: env.contract.address.to_string(),
+                token_addr: x.token_addr.to_string(),
+                vesting_params,
+                start_time: Uint128::zero()
+            }
+        )?,
+        funds: vec![]
+    };
+    Ok(vec![CosmosMsg::Wasm(add_vesting_project)])
+}
 
-pub fn try_removecommunitymember(deps:DepsMut, wallet: String)
+pub fn try_removecommunitymember(deps:DepsMut, info:MessageInfo, wallet: String)
     -> Result<Response, ContractError>
 {
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::CommunityManager)?;
+
     let wallet = deps.api.addr_validate(&wallet).unwrap();
 
     let mut community = COMMUNITY.load(deps.storage).unwrap();
@@ -443,9 +1164,12 @@ pub fn try_removecommunitymember(deps:DepsMut, wallet: String)
     )
 }
 
-pub fn try_addcommunitymember(deps:DepsMut, wallet: String)
+pub fn try_addcommunitymember(deps:DepsMut, info:MessageInfo, wallet: String)
     -> Result<Response, ContractError>
 {
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::CommunityManager)?;
+
     let wallet = deps.api.addr_validate(&wallet).unwrap();
 
     let mut community = COMMUNITY.load(deps.storage).unwrap();
@@ -464,498 +1188,992 @@ pub fn try_addcommunitymember(deps:DepsMut, wallet: String)
 pub fn try_transferallcoins(deps:DepsMut, _env:Env, info:MessageInfo, wallet:String)
     -> Result<Response, ContractError>
 {
-    //-----------check owner--------------------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    if info.sender != config.owner{
-        return Err(ContractError::Unauthorized{});
-    }
-    //--------get all native coins and ust - 4 ----------------------
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::TreasuryOperator)?;
+    let wallet = deps.api.addr_validate(&wallet)?;
+    let reserve = load_fee_config(deps.storage)?.reserve;
+
+    //--------escrow, held assets and deposits stay, only accrued fees go----------
     let balance: AllBalanceResponse = deps.querier.query(
         &QueryRequest::Bank(BankQuery::AllBalances {
             address: _env.contract.address.to_string(),
         }
     ))?;
 
+    let mut fees = ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default();
     let mut nativecoins:Vec<Coin> = Vec::new();
-    for mut x in balance.amount
-    {
-        if x.denom == "uusd" {
-            if x.amount.u128() < 4000000 {
-                return Err(ContractError::NeedCoin{});
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    for fee in fees.iter_mut() {
+        match &fee.info {
+            AssetInfo::Native{ denom } => {
+                let held = balance.amount.iter()
+                    .find(|coin| &coin.denom == denom)
+                    .map_or(Uint128::zero(), |coin| coin.amount);
+                //------uusd reserve is left in contract-------------
+                let kept = if fee.info.is_uusd() { reserve } else { Uint128::zero() };
+                let amount = std::cmp::min(fee.amount, held.saturating_sub(kept));
+                if amount > Uint128::zero() {
+                    nativecoins.push(Coin::new(amount.u128(), denom.clone()));
+                    fee.amount -= amount;
+                }
+            }
+            AssetInfo::Cw20{ .. } => { //------retained cw20 fees go as they are----
+                msgs.push(fee.transfer_msg(&wallet)?);
+                fee.amount = Uint128::zero();
             }
-            x.amount = Uint128::new(x.amount.u128() - 4000000);
         }
-        nativecoins.push(x);
     }
+    if !nativecoins.is_empty() {
+        msgs.insert(0, CosmosMsg::Bank(BankMsg::Send { 
+            to_address: wallet.to_string(),
+            amount: nativecoins,
+        }));
+    }
+    if msgs.is_empty() {
+        return Err(ContractError::NoAccruedFees{});
+    }
+    fees.retain(|fee| fee.amount > Uint128::zero());
+    ACCRUED_FEES.save(deps.storage, &fees)?;
 
-    let bank_native = BankMsg::Send { 
-        to_address: wallet.clone(),
-        amount: nativecoins,
-    };
+    Ok(Response::new()
+    .add_messages(msgs)
+    .add_attribute("action", "trasnfer all coins")
+    )
+}
+pub fn try_updateprojectmetadata(deps:DepsMut, info:MessageInfo, project_id:Uint128,
+    project_metadata: MetadataMsg)
+    -> Result<Response, ContractError>
+{
+    let x:ProjectState = projects().load(deps.storage, project_id.u128().into())?;
 
-    //--------transfer all aust--------------------------------
-    let config = CONFIG.load(deps.storage).unwrap();
+    //-----------only creator of the project------------
+    if info.sender != x.creator_wallet {
+        return Err(ContractError::Unauthorized{});
+    }
 
-    let aust_balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
-        config.aust_token.clone(),
-        &Cw20QueryMsg::Balance{
-            address: _env.contract.address.to_string(),
+    //-----------creation time never changes------------
+    let metadata = validate_metadata_msg(project_metadata, x.metadata.created_at())?;
+    let changed = changed_fields(&x.metadata, &metadata);
+
+    projects().update(deps.storage, project_id.u128().into(), |op| match op {
+        None => Err(ContractError::NotRegisteredProject {}),
+        Some(mut project) => {
+            project.metadata = metadata;
+            Ok(project)
         }
-    )?;
-    let bank_aust = WasmMsg::Execute {
-        contract_addr: String::from(config.aust_token),
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: wallet,
-            amount: aust_balance.balance,
-        }).unwrap(),
-        funds: Vec::new()
-    };
+    })?;
+
+    let mut event = Event::new("project_metadata_changed")
+        .add_attribute("project_id", project_id);
+    for field in changed {
+        event = event.add_attribute("field", field);
+    }
 
     Ok(Response::new()
-    .add_messages(vec![
-        CosmosMsg::Bank(bank_native),
-        CosmosMsg::Wasm(bank_aust)])
-    .add_attribute("action", "trasnfer all coins")
-    )
+        .add_event(event)
+        .add_attribute("action", "Update project metadata"))
 }
 pub fn try_removeproject(deps:DepsMut, info:MessageInfo, project_id:Uint128)
     -> Result<Response, ContractError>
 {
-    //-----------check owner--------------------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    if info.sender != config.owner
-    {
-        return Err(ContractError::Unauthorized{});
-    }
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
 
-    return remove_project(deps, project_id);
+    remove_project(deps, project_id)
 }
 pub fn remove_project(deps:DepsMut, _project_id:Uint128)
     ->Result<Response, ContractError>
 {
-    let res = PROJECTSTATES.may_load(deps.storage, _project_id.u128().into());
+    let res = projects().may_load(deps.storage, _project_id.u128().into());
     if res == Ok(None) {
         return Err(ContractError::NotRegisteredProject {});
     }
-    PROJECTSTATES.remove(deps.storage, U128Key::new(_project_id.u128()));
+    let x = projects().load(deps.storage, _project_id.u128().into())?;
+    projects().remove(deps.storage, U128Key::new(_project_id.u128()))?;
+
+    //-----------backers, milestones and team of project------------
+    for backer in load_backers(deps.storage, _project_id)? {
+        let key = (U128Key::new(_project_id.u128()), backer.backer_wallet);
+        backers().remove(deps.storage, key.clone())?;
+        CONTRIBUTIONS.remove(deps.storage, key);
+    }
+    for step in 0..x.milestone_count.u128() {
+        MILESTONES.remove(deps.storage, 
+            (U128Key::new(_project_id.u128()), U128Key::new(step)));
+    }
+    TEAMMEMBERS.remove(deps.storage, U128Key::new(_project_id.u128()));
+    PROJECT_HISTORY.remove(deps.storage, U128Key::new(_project_id.u128()));
     Ok(Response::new())
 }
 pub fn try_setconfig(deps:DepsMut, _env:Env, info:MessageInfo,
-    admin: Option<String>, 
     wefund: Option<String>, 
-    anchor_market: Option<String>, 
-    aust_token: Option<String>,
-    vesting_contract: Option<String>
+    yield_strategy: Option<YieldStrategy>,
+    vesting_contract: Option<String>,
+    timelock: Option<Uint128>
 ) -> Result<Response, ContractError>
 {
     //-----------check owner--------------------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    if info.sender != config.owner {
-        return Err(ContractError::Unauthorized{});
-    }
+    check_onlyowner(deps.storage, info.sender.clone())?;
     
-    let mut config = CONFIG.load(deps.storage).unwrap();
-
-    config.owner =  admin
-    .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
-    .unwrap_or(config.owner);
+    let config = CONFIG.load(deps.storage).unwrap();
 
-    config.wefund = wefund
+    let wefund = wefund
         .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
         .unwrap_or(config.wefund);
 
-    config.anchor_market = anchor_market
-        .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
-        .unwrap_or(config.anchor_market);
-
-    config.aust_token = aust_token
-        .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
-        .unwrap_or(config.aust_token);
+    let yield_strategy = match yield_strategy {
+        Some(strategy) => validate_strategy(deps.api, strategy)?,
+        None => config.yield_strategy
+    };
 
-    config.vesting_contract = vesting_contract
+    let vesting_contract = vesting_contract
         .and_then(|s| deps.api.addr_validate(s.as_str()).ok()) 
         .unwrap_or(config.vesting_contract);
 
-    CONFIG.save(deps.storage, &config)?;
+    //-----------queue change until current timelock passed------------
+    let execute_after = Uint128::from(_env.block.time.seconds()) + config.timelock;
+    let pending = PendingConfig{
+        wefund,
+        yield_strategy,
+        vesting_contract,
+        timelock: timelock.unwrap_or(config.timelock),
+        execute_after,
+    };
+    PENDING_CONFIG.save(deps.storage, &pending)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "SetConfig")
+        .add_attribute("execute_after", execute_after))
+}
+pub fn try_executeconfig(deps:DepsMut, _env:Env, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    //-----------check owner--------------------------
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    let pending = PENDING_CONFIG.may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingConfig{})?;
+    if Uint128::from(_env.block.time.seconds()) < pending.execute_after {
+        return Err(ContractError::TimelockNotExpired{ execute_after: pending.execute_after });
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.wefund = pending.wefund;
+        config.yield_strategy = pending.yield_strategy;
+        config.vesting_contract = pending.vesting_contract;
+        config.timelock = pending.timelock;
+        Ok(config)
+    })?;
+    PENDING_CONFIG.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "Execute config"))
+}
+pub fn try_cancelconfig(deps:DepsMut, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    //-----------check owner--------------------------
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    if PENDING_CONFIG.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingConfig{});
+    }
+    PENDING_CONFIG.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "Cancel config"))
+}
+pub fn try_proposeowner(deps:DepsMut, info:MessageInfo, owner: String)
+    -> Result<Response, ContractError>
+{
+    //-----------check owner--------------------------
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    let owner = deps.api.addr_validate(&owner)?;
+    PENDING_OWNER.save(deps.storage, &owner)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "Propose owner")
+        .add_attribute("owner", owner))
+}
+pub fn try_acceptownership(deps:DepsMut, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    //-----------only proposed owner accepts------------
+    let pending = PENDING_OWNER.may_load(deps.storage)?
+        .ok_or(ContractError::NoPendingOwner{})?;
+    if info.sender != pending {
+        return Err(ContractError::Unauthorized{});
+    }
+
+    CONFIG.update(deps.storage, |mut config| -> Result<_, ContractError> {
+        config.owner = pending.clone();
+        Ok(config)
+    })?;
+    PENDING_OWNER.remove(deps.storage);
+
+    Ok(Response::new()
+        .add_attribute("action", "Accept ownership")
+        .add_attribute("owner", pending))
+}
+pub fn try_cancelownershipproposal(deps:DepsMut, info:MessageInfo)
+    -> Result<Response, ContractError>
+{
+    //-----------check owner--------------------------
+    check_onlyowner(deps.storage, info.sender.clone())?;
+
+    if PENDING_OWNER.may_load(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner{});
+    }
+    PENDING_OWNER.remove(deps.storage);
 
     Ok(Response::new()
-        .add_attribute("action", "SetConfig"))                                
+        .add_attribute("action", "Cancel ownership proposal"))
 }
 pub fn try_completeproject(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
+    info: MessageInfo,
     _project_id: Uint128
 ) -> Result<Response, ContractError>
 {
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::ProjectReviewer)?;
+
     //--------Get project info----------------------------
-    let x:ProjectState = PROJECTSTATES.load(deps.storage, _project_id.u128().into())?;
+    let mut x:ProjectState = projects().load(deps.storage, _project_id.u128().into())?;
 
     //--------Checking project status-------------------------
     if x.project_status != ProjectStatus::Releasing{//only releasing status
-        return Err(ContractError::NotCorrectStatus{status: x.project_status as u32});
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
 
-    //---------calc hope to release amount---------------------------
-    let mut release_amount: u128 = x.communitybacked_amount.u128() + x.backerbacked_amount.u128();
-    
-    for i in 0..(x.project_milestonestep.u128() as usize){
-        release_amount -= x.milestone_states[i].milestone_amount.u128() * UST;
-    }
-
-    //---------calc total deposited to anchor----------------
-    //----------map to vec-----------------------
-    let all: StdResult<Vec<_>> = PROJECTSTATES.range(deps.storage, None, None, 
-        cosmwasm_std::Order::Ascending).collect();
-    let all = all.unwrap();
-
-    let mut total_deposited = 0;
-    for x in all{
-        let prj = x.1;
-        total_deposited += prj.communitybacked_amount.u128() + prj.backerbacked_amount.u128();
+    //----------load config and pick project yield strategy-----------------
+    let config = CONFIG.load(deps.storage).unwrap();
+    let strategy = project_strategy(&config, &x);
 
-        for i in 0..(prj.project_milestonestep.u128() as usize){
-            total_deposited -= prj.milestone_states[i].milestone_amount.u128() * UST;
-        }
+    //----------redeem all shares left in project escrow, yield included------
+    x.held_assets.retain(|asset| asset.amount > Uint128::zero());
+    if x.escrow_shares == Uint128::zero() && x.held_assets.is_empty() {
+        return Err(ContractError::EmptyEscrow{});
+    }
+    //---------send to creator wallet-------------
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let mut exchange_rate: u128 = 0;
+    if x.escrow_shares > Uint128::zero() {
+        let withdrawal = get_adapter(&strategy).withdraw_shares(&deps.querier, x.escrow_shares)?;
+        exchange_rate = withdrawal.exchange_rate;
+        msgs.extend(withdrawal.msgs);
+        msgs.push(CosmosMsg::Bank(BankMsg::Send { 
+            to_address: x.creator_wallet.to_string(),
+            amount: vec![Coin::new(withdrawal.amount.u128(), "uusd")] 
+        }));
+    }
+    for held in x.held_assets.iter() {
+        msgs.push(held.transfer_msg(&x.creator_wallet)?);
     }
-    //----------load config and read aust token address-----------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    
-    //--------get aust balance---------------------
-    let aust_balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
-        config.aust_token.clone(),
-        &Cw20QueryMsg::Balance{
-            address: _env.contract.address.to_string(),
-        }
-    )?;
 
-    //----------calc declaim aust amount---aust*(release/total)-----------
-    let mut estimate_exchange_rate = total_deposited * UST/aust_balance.balance.u128();
+    x.escrow_shares = Uint128::zero();
+    x.escrow_principal = Uint128::zero();
+    x.held_assets = Vec::new();
 
-    //--------get exchange rate between ust and aust ---------------------
-    let epoch: EpochStateResponse = deps.querier.query_wasm_smart(
-        config.anchor_market.to_string(),
-        &AnchorQuery::EpochState{
-            block_height: None,
-            distributed_interest: None,
+    //-----------paid out, project is done---------------------
+    transition_status(deps.storage, &env, &mut x, ProjectStatus::Done)?;
+    projects().update(deps.storage, _project_id.u128().into(), |op| match op {
+        None => Err(ContractError::NotRegisteredProject {}),
+        Some(mut project) => {
+            project.escrow_principal = x.escrow_principal;
+            project.escrow_shares = x.escrow_shares;
+            project.held_assets = x.held_assets.clone();
+            project.project_status = x.project_status.clone();
+            Ok(project)
         }
-    )?;
-    let epoch_exchange_rate = convert_str_int(epoch.exchange_rate.to_string());
-    
-    if estimate_exchange_rate < epoch_exchange_rate{
-        estimate_exchange_rate = epoch_exchange_rate;
-    }
-
-    let withdraw_amount = release_amount * UST / estimate_exchange_rate;
-    let release_amount = withdraw_amount * epoch_exchange_rate / UST;
-
-    //----ask aust_token for transfer to anchor martket and execute redeem_stable ----------
-    let withdraw = WasmMsg::Execute {
-        contract_addr: String::from(config.aust_token),
-        msg: to_binary(&Cw20ExecuteMsg::Send {
-            contract: config.anchor_market.to_string(),
-            msg: to_binary(&Cw20HookMsg::RedeemStable{}).unwrap(), //redeem_stable{}
-            amount: Uint128::new(withdraw_amount)
-        }).unwrap(),
-        funds: Vec::new()
-    };
-
-    //---------send to creator wallet-------------
-    let ust_release = Coin::new(release_amount, "uusd");
-    let send2_creator = BankMsg::Send { 
-        to_address: x.creator_wallet.to_string(),
-        amount: vec![ust_release] 
-    };
+    })?;
 
     Ok(Response::new()
-    .add_messages(vec![
-        CosmosMsg::Wasm(withdraw),
-        CosmosMsg::Bank(send2_creator)])
+    .add_messages(msgs)
     .add_attribute("action", "complete project")
-    .add_attribute("epoch_exchange_rate", epoch.exchange_rate.to_string())
+    .add_attribute("exchange_rate", exchange_rate.to_string())
     )
 }
 pub fn try_failproject(
     deps: DepsMut,
     _env: Env,
+    info: MessageInfo,
     _project_id: Uint128
 ) -> Result<Response, ContractError>
 {
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::ProjectReviewer)?;
+
     //--------Get project info----------------------------
-    let x:ProjectState = PROJECTSTATES.load(deps.storage, _project_id.u128().into())?;
+    let x:ProjectState = projects().load(deps.storage, _project_id.u128().into())?;
 
     //--------Checking project status-------------------------
     if x.project_status != ProjectStatus::Releasing{//only releasing status
-        return Err(ContractError::NotCorrectStatus{status: x.project_status as u32});
-    }
-
-    //---------calc hope to release amount---------------------------
-    let mut release_amount: u128 = x.communitybacked_amount.u128() + x.backerbacked_amount.u128();
-    
-    for i in 0..(x.project_milestonestep.u128() as usize){
-        release_amount -= x.milestone_states[i].milestone_amount.u128() * UST;
-    }
-
-    //---------calc total deposited to anchor----------------
-    //----------map to vec-----------------------
-    let all: StdResult<Vec<_>> = PROJECTSTATES.range(deps.storage, None, None, 
-        cosmwasm_std::Order::Ascending).collect();
-    let all = all.unwrap();
-
-    let mut total_deposited = 0;
-    for x in all{
-        let prj = x.1;
-        total_deposited += prj.communitybacked_amount.u128() + prj.backerbacked_amount.u128();
-
-        for i in 0..(prj.project_milestonestep.u128() as usize){
-            total_deposited -= prj.milestone_states[i].milestone_amount.u128() * UST;
-        }
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
-    //----------load config and read aust token address-----------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    
-    //--------get aust balance---------------------
-    let aust_balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
-        config.aust_token.clone(),
-        &Cw20QueryMsg::Balance{
-            address: _env.contract.address.to_string(),
-        }
-    )?;
-
-    //----------calc declaim aust amount---aust*(release/total)-----------
-    let mut estimate_exchange_rate = total_deposited * UST/aust_balance.balance.u128();
 
-    //--------get exchange rate between ust and aust ---------------------
-    let epoch: EpochStateResponse = deps.querier.query_wasm_smart(
-        config.anchor_market.to_string(),
-        &AnchorQuery::EpochState{
-            block_height: None,
-            distributed_interest: None,
-        }
-    )?;
-    let epoch_exchange_rate = convert_str_int(epoch.exchange_rate.to_string());
-    
-    if estimate_exchange_rate < epoch_exchange_rate{
-        estimate_exchange_rate = epoch_exchange_rate;
+    //----------nothing left in escrow to refund------
+    if x.escrow_shares == Uint128::zero() 
+        && x.held_assets.iter().all(|asset| asset.amount == Uint128::zero()) {
+        return Err(ContractError::EmptyEscrow{});
     }
 
-    let withdraw_amount = release_amount * UST / estimate_exchange_rate;
-    let release_amount = withdraw_amount * epoch_exchange_rate / UST;
-
-    let mut msg= Vec::new();
-
-    //----ask aust_token for transfer to anchor martket and execute redeem_stable ----------
-    let withdraw = WasmMsg::Execute {
-        contract_addr: String::from(config.aust_token),
-        msg: to_binary(&Cw20ExecuteMsg::Send {
-            contract: config.anchor_market.to_string(),
-            msg: to_binary(&Cw20HookMsg::RedeemStable{}).unwrap(), //redeem_stable{}
-            amount: Uint128::new(withdraw_amount)
-        }).unwrap(),
-        funds: Vec::new()
-    };
-
-    msg.push(CosmosMsg::Wasm(withdraw));
-
-    //---------send to backer wallet-------------
-    for backer in x.backer_states{
-        let mut backed_ust = backer.ust_amount.clone(); 
-
-        //---while mistone releasing, suddenly failed, distribute with %
-        backed_ust.amount = Uint128::new(backer.ust_amount.amount.u128() * release_amount
-            /(x.communitybacked_amount.u128() + x.backerbacked_amount.u128()));
-
-        let send2_backer = BankMsg::Send { 
-            to_address: backer.backer_wallet.to_string(),
-            amount: vec![backed_ust] 
-        };
-        msg.push(CosmosMsg::Bank(send2_backer));
-    }
-    
-    //-----update project state to FAIL----------------------------
-    PROJECTSTATES.update(deps.storage, _project_id.u128().into(), |op| match op {
-        None => Err(ContractError::NotRegisteredProject {}),
-        Some(mut project) => {
-            project.project_status = ProjectStatus::Fail; //fail
-            Ok(project)
-        }
-    })?;
+    //-----update project state to FAIL, record refund of every backer---------
+    fail_project(deps.storage, &_env, x)?;
 
     Ok(Response::new()
-    .add_messages(msg)
     .add_attribute("action", "project failed")
     )
 }
 
 pub fn try_addproject(
-    deps:DepsMut,
+    mut deps:DepsMut,
     _env: Env,
     _info: MessageInfo,
-    _project_company: String,
-    _project_title: String,
-    _project_description: String,
-    _project_ecosystem: String,
-    _project_createddate: String,
-    _project_saft: String,
-    _project_logo: String,
-    _project_whitepaper: String,
-    _project_website: String,
-    _project_email: String,
     _creator_wallet: String,
-    _project_collected: Uint128,
-    _project_milestones: Vec<Milestone>,
-    _project_teammembers: Vec<TeamMember>,
-    _vesting: Vec<VestingParameter>,
-    _token_addr: String,
+    params: ProjectParams,
+) -> Result<Response, ContractError> 
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, _info.sender.clone(), Role::ProjectReviewer)?;
+
+    let res = add_project(deps.branch(), _env.clone(), _creator_wallet, params)?;
+
+    //-----------community votes on listing right away-----------
+    let project_id = PROJECT_SEQ.load(deps.storage)?;
+    open_listingvote(deps.storage, &_env, project_id)?;
+    Ok(res)
+}
+pub fn add_project(
+    deps:DepsMut,
+    _env: Env,
+    _creator_wallet: String,
+    params: ProjectParams,
 ) -> Result<Response, ContractError> 
 {
-    let token_addr = deps.api.addr_validate(_token_addr.as_str())
+    let milestone_voting = params.milestone_voting.unwrap_or(MilestoneVoting{
+        quorum: Uint128::new(DEFAULT_QUORUM),
+        threshold: Uint128::new(DEFAULT_THRESHOLD),
+        vote_period: Uint128::new(DEFAULT_VOTE_PERIOD),
+        max_rejections: Uint128::new(DEFAULT_MAX_REJECTIONS),
+    });
+    if milestone_voting.quorum > Uint128::new(100) 
+        || milestone_voting.threshold > Uint128::new(100)
+        || milestone_voting.vote_period == Uint128::zero()
+    {
+        return Err(ContractError::InvalidMilestoneVoting{});
+    }
+
+    //----------uusd unless project accepts other whitelisted assets-----------
+    let config = CONFIG.load(deps.storage)?;
+    let mut accepted_assets = Vec::new();
+    for asset in params.accepted_assets.unwrap_or(vec![AssetInfo::uusd()]) {
+        let asset = match asset {
+            AssetInfo::Cw20{ contract_addr } => AssetInfo::Cw20{
+                contract_addr: deps.api.addr_validate(contract_addr.as_str())?
+            },
+            native => native,
+        };
+        if config.asset_rate(&asset).is_none() {
+            return Err(ContractError::NotAcceptedAsset{ asset: asset.to_string() });
+        }
+        accepted_assets.push(asset);
+    }
+    if accepted_assets.is_empty() {
+        return Err(ContractError::NeedCoin{});
+    }
+
+    //----------token allocation divides by stage price-----------
+    if params.vesting.iter().any(|param| param.stage_price == Uint128::zero()) {
+        return Err(ContractError::InvalidStagePrice{});
+    }
+
+    let token_addr = deps.api.addr_validate(params.token_addr.as_str())
         .unwrap_or(Addr::unchecked("".to_string()));
 
+    let metadata = validate_metadata_msg(params.project_metadata, 
+        Uint128::from(_env.block.time.seconds()))?;
+
     let mut new_project:ProjectState = ProjectState{
-        project_company: _project_company,
-        project_title: _project_title,
-        project_description: _project_description,
-        project_ecosystem: _project_ecosystem,
-        project_createddate: _project_createddate,
-        project_saft: _project_saft,
-        project_logo: _project_logo,
-        project_whitepaper: _project_whitepaper,
-        project_website: _project_website,
-        project_email: _project_email,
+        metadata,
 
         project_id: Uint128::zero(), //auto increment
-        creator_wallet: deps.api.addr_validate(&_creator_wallet).unwrap(),
-        project_collected: _project_collected,
+        creator_wallet: deps.api.addr_validate(&_creator_wallet)?,
+        project_collected: params.project_collected,
         project_status: ProjectStatus::WefundVote,
         fundraising_stage: Uint128::zero(),
+        fundraising_deadline: params.fundraising_deadline,
+        fundraising_rounds: Vec::new(),
 
         backerbacked_amount: Uint128::zero(),
         communitybacked_amount: Uint128::zero(),
-
-        backer_states: Vec::new(),
-        communitybacker_states: Vec::new(),
-
-        milestone_states: _project_milestones,
+        backer_count: Uint128::zero(),
+        communitybacker_count: Uint128::zero(),
+        wallet_min_amount: None,
+        wallet_max_amount: None,
+        accepted_assets,
+        backed_assets: Vec::new(),
+        held_assets: Vec::new(),
+
+        milestone_count: Uint128::new(params.project_milestones.len() as u128),
         project_milestonestep: Uint128::zero(), //first milestonestep
+        milestone_voting,
 
-        teammember_states: _project_teammembers,
-
-        vesting: _vesting.clone(),
+        stage_allocated: vec![Uint128::zero(); params.vesting.len()],
+        vesting: params.vesting,
         token_addr: token_addr.clone(),
+        yield_strategy: None,
+        fee_bps: None,
+        escrow_principal: Uint128::zero(),
+        escrow_shares: Uint128::zero(),
     };
 
     save_projectstate(deps.storage, &mut new_project)?;
 
-    let config = CONFIG.load(deps.storage)?;
-    if config.vesting_contract != "".to_string() && token_addr != "".to_string()
-    {
-        let mut vesting_params: Vec<VestingParam> = Vec::new();
-        for param in _vesting{
-            vesting_params.push(VestingParam{
-                soon: param.stage_soon,
-                after: param.stage_after,
-                period: param.stage_period,
-            })
-        }
-        //----------add fundraising project------------------------
-        let add_vesting_project = WasmMsg::Execute {
-            contract_addr: config.vesting_contract.to_string(),
-            msg: to_binary(
-                &VestingMsg::AddProject {
-                    project_id: new_project.project_id,
-                    admin: _env.contract.address.to_string(),
-                    token_addr: token_addr.to_string(),
-                    vesting_params: vesting_params,
-                    start_time: Uint128::zero()
+    //----------milestones and team kept beside the project------------
+    for (step, milestone) in params.project_milestones.iter().enumerate() {
+        save_milestone(deps.storage, new_project.project_id, 
+            Uint128::new(step as u128), milestone)?;
+    }
+    TEAMMEMBERS.save(deps.storage, new_project.project_id.u128().into(), 
+        &params.project_teammembers)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "add project")
+        .add_attribute("id", new_project.project_id))
+}
+pub fn try_submitproject(
+    mut deps:DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    params: ProjectParams,
+) -> Result<Response, ContractError> 
+{
+    //-----------exactly the deposit, in uusd-------------------
+    let deposit = SUBMIT_DEPOSIT.may_load(deps.storage)?
+        .unwrap_or(Uint128::new(DEFAULT_SUBMIT_DEPOSIT));
+    let paid = match info.funds.as_slice() {
+        [] => Uint128::zero(),
+        [coin] if coin.denom == "uusd" => coin.amount,
+        _ => return Err(ContractError::WrongDeposit{ deposit }),
+    };
+    if paid != deposit {
+        return Err(ContractError::WrongDeposit{ deposit });
+    }
+
+    //-----------project of sender, waiting for review---------------
+    let res = add_project(deps.branch(), _env.clone(), info.sender.to_string(), params)?;
+
+    let project_id = PROJECT_SEQ.load(deps.storage)?;
+    let x = projects().load(deps.storage, project_id.u128().into())?;
+    reviews().save(deps.storage, project_id.u128().into(), &Review{
+        project_id,
+        creator_wallet: info.sender,
+        title: x.metadata.title().to_string(),
+        status: ReviewStatus::Submitted,
+        deposit,
+        slashed: false,
+        comments: Vec::new(),
+    })?;
+
+    Ok(res.add_attribute("deposit", deposit))
+}
+pub fn try_reviewproject(mut deps:DepsMut, env:Env, info:MessageInfo, project_id:Uint128,
+    decision: ReviewDecision, comment: String, fee_bps: Option<Uint128>)
+    -> Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::ProjectReviewer)?;
+
+    let mut review = reviews().load(deps.storage, project_id.u128().into())?;
+
+    //-----------reject also while waiting on changes, rest only when submitted---------
+    let allowed = match decision {
+        ReviewDecision::Reject{..} => review.status == ReviewStatus::Submitted 
+            || review.status == ReviewStatus::ChangesRequested,
+        _ => review.status == ReviewStatus::Submitted,
+    };
+    if !allowed {
+        return Err(ContractError::NotCorrectReviewStatus{});
+    }
+
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    let deposit = Coin::new(review.deposit.u128(), "uusd");
+    match decision {
+        ReviewDecision::RequestChanges => {
+            review.status = ReviewStatus::ChangesRequested;
+        },
+        ReviewDecision::Approve => {
+            //-------fee rate kept for listing, community votes on it-------
+            if let Some(bps) = fee_bps {
+                if bps > Uint128::new(BPS) {
+                    return Err(ContractError::InvalidFeeConfig{});
                 }
-            ).unwrap(),
-            funds: vec![]
-        };
+            }
+            projects().update(deps.storage, project_id.u128().into(), |op| match op {
+                None => Err(ContractError::NotRegisteredProject {}),
+                Some(mut project) => {
+                    project.fee_bps = fee_bps;
+                    Ok(project)
+                }
+            })?;
+            open_listingvote(deps.storage, &env, project_id)?;
+            review.status = ReviewStatus::Approved;
+            if !deposit.amount.is_zero() {
+                msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address: review.creator_wallet.to_string(),
+                    amount: vec![deposit],
+                }));
+            }
+        },
+        ReviewDecision::Reject{ slash } => {
+            remove_project(deps.branch(), project_id)?;
+            review.status = ReviewStatus::Rejected;
+            review.slashed = slash;
+
+            //-------spam deposit goes to wefund------------
+            let to_address = if slash {
+                CONFIG.load(deps.storage)?.wefund.to_string()
+            } else {
+                review.creator_wallet.to_string()
+            };
+            if !deposit.amount.is_zero() {
+                msgs.push(CosmosMsg::Bank(BankMsg::Send {
+                    to_address,
+                    amount: vec![deposit],
+                }));
+            }
+        },
+    }
 
-        return Ok(Response::new()
-            .add_messages(vec![CosmosMsg::Wasm(add_vesting_project)])
-            .add_attribute("action", "add project")
-            .add_attribute("id", new_project.project_id)
-            );
+    review.comments.push(ReviewComment{
+        author: info.sender,
+        status: review.status.clone(),
+        comment,
+        timestamp: Uint128::from(env.block.time.seconds()),
+    });
+    reviews().save(deps.storage, project_id.u128().into(), &review)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "review project")
+        .add_attribute("status", format!("{:?}", review.status)))
+}
+pub fn try_resubmitproject(deps:DepsMut, env:Env, info:MessageInfo, project_id:Uint128, comment:String)
+    -> Result<Response, ContractError>
+{
+    let mut review = reviews().load(deps.storage, project_id.u128().into())?;
+
+    //-----------only creator, after changes were requested---------
+    if review.creator_wallet != info.sender {
+        return Err(ContractError::Unauthorized{});
+    }
+    if review.status != ReviewStatus::ChangesRequested {
+        return Err(ContractError::NotCorrectReviewStatus{});
+    }
+
+    review.status = ReviewStatus::Submitted;
+    review.comments.push(ReviewComment{
+        author: info.sender,
+        status: ReviewStatus::Submitted,
+        comment,
+        timestamp: Uint128::from(env.block.time.seconds()),
+    });
+    reviews().save(deps.storage, project_id.u128().into(), &review)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "resubmit project"))
+}
+pub fn open_listingvote(store: &mut dyn Storage, env: &Env, project_id: Uint128)
+    -> Result<(), ContractError>
+{
+    let voting = LISTING_VOTING.may_load(store)?.unwrap_or_default();
+    let now = Uint128::from(env.block.time.seconds());
+    let community = COMMUNITY.may_load(store)?.unwrap_or_default();
+
+    LISTING_VOTES.save(store, project_id.u128().into(), &ListingVote{
+        project_id,
+        votestart: now,
+        voteend: now + voting.vote_period,
+        snapshot_height: env.block.height,
+        community,
+        votes: Vec::new(),
+        passed: None,
+    })?;
+    Ok(())
+}
+pub fn try_castlistingvote(deps:DepsMut, env:Env, info:MessageInfo, project_id:Uint128, voted:bool)
+    -> Result<Response, ContractError>
+{
+    let x = projects().load(deps.storage, project_id.u128().into())?;
+    if x.project_status != ProjectStatus::WefundVote {
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
+    }
+
+    //-------check voting window-------------------
+    let mut vote = LISTING_VOTES.may_load(deps.storage, project_id.u128().into())?
+        .ok_or(ContractError::VotingClosed{})?;
+    let now = Uint128::from(env.block.time.seconds());
+    if now < vote.votestart || now > vote.voteend {
+        return Err(ContractError::VotingClosed{});
+    }
+
+    //-------weight from configured source-------------------
+    let voting = LISTING_VOTING.may_load(deps.storage)?.unwrap_or_default();
+    let weight = get_weight_source(&voting.weight_source)
+        .weight(deps.as_ref(), &info.sender, &vote)?;
+    if weight.is_zero() {
+        return Err(ContractError::NotEligibleVoter{});
+    }
+
+    match vote.votes.iter_mut().find(|vote| vote.wallet == info.sender) {
+        Some(vote) => {
+            vote.voted = Some(voted);
+            vote.weight = weight;
+        },
+        None => vote.votes.push(Vote{
+            wallet: info.sender,
+            voted: Some(voted),
+            weight,
+            delegate: None,
+        }),
+    }
+    LISTING_VOTES.save(deps.storage, project_id.u128().into(), &vote)?;
+
+    Ok(Response::new()
+        .add_attribute("action", "cast listing vote")
+        .add_attribute("weight", weight))
+}
+pub fn try_tallylistingvote(deps:DepsMut, env:Env, project_id:Uint128)
+    -> Result<Response, ContractError>
+{
+    let mut x = projects().load(deps.storage, project_id.u128().into())?;
+    if x.project_status != ProjectStatus::WefundVote {
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
+    }
+
+    let mut vote = LISTING_VOTES.may_load(deps.storage, project_id.u128().into())?
+        .ok_or(ContractError::VotingClosed{})?;
+    if Uint128::from(env.block.time.seconds()) <= vote.voteend {
+        return Err(ContractError::VotingNotEnded{});
+    }
+
+    //-------sum cast and yes weight, total weight is base of quorum-------------
+    let voting = LISTING_VOTING.may_load(deps.storage)?.unwrap_or_default();
+    let total_weight = get_weight_source(&voting.weight_source)
+        .total_weight(deps.as_ref(), &vote)?;
+    let mut cast_weight = Uint128::zero();
+    let mut yes_weight = Uint128::zero();
+    for vote in vote.votes.iter() {
+        cast_weight += vote.weight;
+        if vote.voted == Some(true) {
+            yes_weight += vote.weight;
+        }
+    }
+
+    let quorum_reached = cast_weight * Uint128::new(100) >= total_weight * voting.quorum;
+    let passed = quorum_reached && yes_weight > Uint128::zero()
+        && yes_weight * Uint128::new(100) >= cast_weight * voting.threshold;
+
+    //-------list for fundraising or reject-------------------------
+    let mut msgs: Vec<CosmosMsg> = Vec::new();
+    if passed {
+        msgs = approve_project(deps.storage, &env, project_id, x.fee_bps)?;
+    } else {
+        transition_status(deps.storage, &env, &mut x, ProjectStatus::Rejected)?;
+        projects().update(deps.storage, project_id.u128().into(), |op| match op {
+            None => Err(ContractError::NotRegisteredProject {}),
+            Some(mut project) => {
+                project.project_status = x.project_status.clone();
+                Ok(project)
+            }
+        })?;
+    }
+    vote.passed = Some(passed);
+    LISTING_VOTES.save(deps.storage, project_id.u128().into(), &vote)?;
+
+    Ok(Response::new()
+        .add_messages(msgs)
+        .add_attribute("action", "tally listing vote")
+        .add_attribute("passed", passed.to_string()))
+}
+pub fn try_setlistingvoting(deps:DepsMut, info:MessageInfo, listing_voting: ListingVoting)
+    -> Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
+
+    if listing_voting.quorum > Uint128::new(100) 
+        || listing_voting.threshold > Uint128::new(100)
+        || listing_voting.vote_period == Uint128::zero()
+    {
+        return Err(ContractError::InvalidListingVoting{});
     }
+    let weight_source = match listing_voting.weight_source {
+        WeightSource::Staking{ contract } => WeightSource::Staking{
+            contract: deps.api.addr_validate(contract.as_str())?
+        },
+        source => source,
+    };
+
+    LISTING_VOTING.save(deps.storage, &ListingVoting{ weight_source, ..listing_voting })?;
+    Ok(Response::new()
+        .add_attribute("action", "set listing voting"))
+}
+pub fn try_setsubmitdeposit(deps:DepsMut, info:MessageInfo, amount:Uint128)
+    -> Result<Response, ContractError>
+{
+    //-----------check role--------------------------
+    check_role(deps.storage, info.sender.clone(), Role::Admin)?;
 
+    SUBMIT_DEPOSIT.save(deps.storage, &amount)?;
     Ok(Response::new()
-        .add_attribute("action", "add project"))
+        .add_attribute("action", "set submit deposit")
+        .add_attribute("amount", amount))
 }
 
 pub fn try_back2project(
     deps: DepsMut, 
     env: Env,
     info: MessageInfo,
-    project_id: Uint128, 
-    backer_wallet: String,
-    fundraising_stage: Uint128,
-    token_amount: Uint128,
-    otherchain: String,
-    otherchain_wallet: String,
+    params: BackParams,
+) -> Result<Response, ContractError> 
+{
+    //--------exactly one native coin----------------------
+    if info.funds.is_empty() {
+        return Err(ContractError::NeedCoin{});
+    }
+    if info.funds.len() > 1 {
+        return Err(ContractError::MultipleDenoms{});
+    }
+    let fund = Asset{
+        info: AssetInfo::Native{ denom: info.funds[0].denom.clone() },
+        amount: info.funds[0].amount,
+    };
+
+    back2project(deps, env, info.sender, fund, params)
+}
+pub fn try_receive(deps: DepsMut, env: Env, info: MessageInfo, cw20_msg: Cw20ReceiveMsg)
+    -> Result<Response, ContractError> 
+{
+    //--------cw20 contract calling is the backed asset-------------
+    let fund = Asset{
+        info: AssetInfo::Cw20{ contract_addr: info.sender },
+        amount: cw20_msg.amount,
+    };
+    let sender = deps.api.addr_validate(&cw20_msg.sender)?;
+
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::Back2Project{ project_id, backer_wallet, fundraising_stage, otherchain, otherchain_wallet } =>
+            back2project(deps, env, sender, fund, BackParams{
+                project_id,
+                backer_wallet,
+                fundraising_stage,
+                otherchain,
+                otherchain_wallet,
+            }),
+    }
+}
+pub fn back2project(
+    deps: DepsMut, 
+    env: Env,
+    sender: Addr,
+    fund: Asset,
+    params: BackParams,
 ) -> Result<Response, ContractError> 
 {
+    let BackParams{ project_id, backer_wallet, fundraising_stage, otherchain, otherchain_wallet } = params;
+
     //-------check project exist-----------------------------------
-    let res = PROJECTSTATES.may_load(deps.storage, project_id.u128().into());
+    let res = projects().may_load(deps.storage, project_id.u128().into());
     if res == Ok(None) { //not exist
         return Err(ContractError::NotRegisteredProject {});
     }
     //--------Get project info------------------------------------
-    let mut x = PROJECTSTATES.load(deps.storage, project_id.u128().into())?;
+    let mut x = projects().load(deps.storage, project_id.u128().into())?;
     if x.project_status != ProjectStatus::Fundraising{//only fundraising status
-        return Err(ContractError::NotCorrectStatus{status: x.project_status as u32});
+        return Err(ContractError::NotCorrectStatus{status: x.project_status});
     }
-
-    //--------check sufficient back--------------------
-    let fee:u128 = 4 * UST;
-    if info.funds.is_empty() || info.funds[0].amount.u128() < 6 * UST{
-        return Err(ContractError::NeedCoin{});
+    if let Some(deadline) = x.fundraising_deadline {
+        if Uint128::from(env.block.time.seconds()) > deadline {
+            return Err(ContractError::FundraisingExpired{});
+        }
     }
- 
-    let fund = info.funds[0].clone();
-    let mut fund_real_back = fund.clone();
-    let mut fund_wefund = fund.clone();
-    //--------calc amount to desposit and to wefund
-    if fund.amount.u128() >= 100 * UST{
-        fund_real_back.amount = Uint128::new(fund.amount.u128() * 100 / 105);
-        fund_wefund.amount = Uint128::new((fund.amount.u128() * 5 / 105) - fee);
+
+    //--------check accepted asset, caps, fees and weights count its uusd value----------
+    let config = CONFIG.load(deps.storage)?;
+    let rate = match config.asset_rate(&fund.info) {
+        Some(rate) if x.accepted_assets.contains(&fund.info) => rate,
+        _ => return Err(ContractError::NotAcceptedAsset{ asset: fund.info.to_string() }),
+    };
+    let value = rate.to_uusd(fund.amount);
+
+    //--------move past rounds ended or filled, backing goes to active round----------
+    let now = Uint128::from(env.block.time.seconds());
+    let round = if x.fundraising_rounds.is_empty() {
+        None
     } else {
-        fund_real_back.amount = Uint128::new(fund.amount.u128() - 5 * UST);
-        fund_wefund.amount = Uint128::new(1 * UST);
+        x.fundraising_stage = active_round(&x.fundraising_rounds, x.fundraising_stage, now);
+        let round = x.fundraising_rounds.get(x.fundraising_stage.u128() as usize)
+            .ok_or(ContractError::NoActiveRound{})?;
+        if now < round.start {
+            return Err(ContractError::RoundNotStarted{ start: round.start });
+        }
+        Some(x.fundraising_stage.u128() as usize)
+    };
+    //--------backing is priced at stage of project, sender must agree on it----------
+    if fundraising_stage != x.fundraising_stage {
+        return Err(ContractError::NotActiveRound{ stage: x.fundraising_stage });
     }
 
-    let backer_wallet = deps.api.addr_validate(&backer_wallet).unwrap();
+    let backer_wallet = deps.api.addr_validate(&backer_wallet)?;
+    let backer_key = (U128Key::new(project_id.u128()), backer_wallet.clone());
+    let backer = backers().may_load(deps.storage, backer_key.clone())?;
+
+    //--------community half is fixed on first backing of wallet----------
+    let is_community = match &backer {
+        Some(backer) => backer.community,
+        None => COMMUNITY.load(deps.storage)?.contains(&backer_wallet),
+    };
 
-    //--------check community and calc backed amount----------------
-    let community = COMMUNITY.load(deps.storage)?;
-    let is_community = community.iter().find(|&x| x == &backer_wallet);
+    //--------check community half and what is left of it----------------
     let collected = Uint128::new(x.project_collected.u128() / 2 * UST);
+    let half_backed = if is_community { x.communitybacked_amount } else { x.backerbacked_amount };
+    if half_backed >= collected {
+        return Err(ContractError::AlreadyCollected{});
+    }
+    let mut cap = collected - half_backed;
 
-    if is_community != None { //community backer
-        if x.communitybacked_amount >= collected{
-            return Err(ContractError::AlreadyCollected{});
+    //--------per wallet maximum, at par------------------
+    let wallet_backed = backer.as_ref().map_or(Uint128::zero(), |backer| backer.ust_amount.amount);
+    if let Some(max) = x.wallet_max_amount {
+        if wallet_backed >= max {
+            return Err(ContractError::WalletLimitReached{});
         }
-        x.communitybacked_amount += fund_real_back.amount;
-    } else { //only backer
-        if x.backerbacked_amount >= collected{
-            return Err(ContractError::AlreadyCollected{});
+        cap = std::cmp::min(cap, max - wallet_backed);
+    }
+    //--------what is left of active round-----------------
+    if let Some(index) = round {
+        let round = &x.fundraising_rounds[index];
+        cap = std::cmp::min(cap, round.cap - round.raised);
+    }
+
+    //--------tokens left in vesting stage, at stage price, bound what is accepted---------
+    let stage = x.fundraising_stage.u128() as usize;
+    if !x.vesting.is_empty() && stage >= x.vesting.len() {
+        return Err(ContractError::NotActiveRound{ stage: x.fundraising_stage });
+    }
+    let allocation = match x.vesting.get(stage) {
+        Some(param) if !x.token_addr.as_str().is_empty() => {
+            let token_info: TokenInfoResponse = deps.querier.query_wasm_smart(
+                x.token_addr.clone(),
+                &Cw20QueryMsg::TokenInfo{}
+            )?;
+            let unit = Uint128::new(10u128.pow(token_info.decimals as u32)); //for decimals
+            x.stage_allocated.resize(x.vesting.len(), Uint128::zero());
+            let left = param.stage_amount.checked_mul(unit).map_err(StdError::from)?
+                .checked_sub(x.stage_allocated[stage]).map_err(StdError::from)?;
+            let left_backing = left.multiply_ratio(param.stage_price, unit);
+            if left_backing == Uint128::zero() {
+                return Err(ContractError::StageSoldOut{ stage: x.fundraising_stage });
+            }
+            cap = std::cmp::min(cap, left_backing);
+            Some((param.stage_price, unit))
+        },
+        _ => None, //no token to allocate yet
+    };
+
+    //--------calc amount to desposit and fee by fee config and project rate-------------
+    //--------only up to cap is accepted, excess goes back to sender-------------
+    let (fee, excess) = calc_capped_fee(&load_fee_config(deps.storage)?, &config.wefund, value,
+        x.fee_bps, is_community, cap)?;
+
+    if let Some(min) = x.wallet_min_amount {
+        if wallet_backed + fee.backed < min {
+            return Err(ContractError::BelowWalletMin{ min });
         }
-        x.backerbacked_amount += fund_real_back.amount;
-    }
-    //------push to new backer------------------
-    let new_baker:BackerState = BackerState{
-        backer_wallet: backer_wallet,
-        otherchain: otherchain,
-        otherchain_wallet: otherchain_wallet,
-        ust_amount: fund_real_back.clone(),
-        aust_amount: Coin::new(0, "aust")
+    }
+
+    //--------back in units of backed asset, rounding dust goes to fee----------
+    let excess = rate.from_uusd(excess);
+    let fund_real_back = Asset{ info: fund.info.clone(), amount: rate.from_uusd(fee.backed) };
+    let payouts: Vec<(Addr, Uint128)> = fee.payouts.iter()
+        .map(|(recipient, amount)| (recipient.clone(), rate.from_uusd(*amount)))
+        .filter(|(_, amount)| *amount > Uint128::zero())
+        .collect();
+    let paid: Uint128 = payouts.iter().map(|(_, amount)| *amount).sum();
+    let retained = fund.amount - fund_real_back.amount - excess - paid;
+
+    //--------token base units for net contribution, rounded down----------
+    let token_amount = match allocation {
+        Some((price, unit)) => fee.backed.multiply_ratio(unit, price),
+        None => Uint128::zero(),
     };
-    if is_community != None {//community backer
-        x.communitybacker_states.push(new_baker);
-    } else {
-        x.backer_states.push(new_baker);
+    if allocation.is_some() {
+        x.stage_allocated[stage] += token_amount;
+    }
+    if is_community { //community backer
+        x.communitybacked_amount += fee.backed;
+    } else { //only backer
+        x.backerbacked_amount += fee.backed;
+    }
+    //------round moves on once filled------------------
+    if let Some(index) = round {
+        x.fundraising_rounds[index].raised += fee.backed;
+        x.fundraising_stage = active_round(&x.fundraising_rounds, x.fundraising_stage, now);
+    }
+    //------count wallet on first backing------------------
+    if backer.is_none() {
+        if is_community {
+            x.communitybacker_count += Uint128::new(1);
+        } else {
+            x.backer_count += Uint128::new(1);
+        }
     }
 
+    //------add to backing of wallet------------------
+    let mut backer = backer.unwrap_or(BackerState{
+        project_id,
+        backer_wallet: backer_wallet.clone(),
+        community: is_community,
+        ust_amount: Coin::new(0, "uusd"),
+        aust_amount: Coin::new(0, "aust"),
+        backed_assets: Vec::new(),
+        otherchain: String::new(),
+        otherchain_wallet: String::new(),
+        refunded: false,
+        refund_shares: Uint128::zero(),
+        refund_assets: Vec::new(),
+        fee_bps: Uint128::zero(),
+        fee_amount: Uint128::zero(),
+        token_amount: Uint128::zero(),
+        delegate: None,
+    });
+    backer.ust_amount.amount += fee.backed;
+    add_asset(&mut backer.backed_assets, &fund_real_back);
+    backer.otherchain = otherchain;
+    backer.otherchain_wallet = otherchain_wallet;
+    backer.fee_bps = fee.bps;
+    backer.fee_amount += fee.fee;
+    backer.token_amount += token_amount;
+    backers().save(deps.storage, backer_key.clone(), &backer)?;
+
+    //------keep each contribution in history of wallet------------
+    let contribution = Contribution{
+        asset: fund_real_back.clone(),
+        fee_amount: fee.fee,
+        fundraising_stage,
+        token_amount,
+        timestamp: Uint128::from(env.block.time.seconds()),
+    };
+    CONTRIBUTIONS.update(deps.storage, backer_key, |history| -> StdResult<_> {
+        let mut history = history.unwrap_or_default();
+        history.push(contribution);
+        Ok(history)
+    })?;
+
     //------check needback-----------------
     let mut communitybacker_needback = true;
     let mut backer_needback = true;
@@ -971,24 +2189,9 @@ pub fn try_back2project(
 
     //---------check collection and switch to releasing status---------
     if communitybacker_needback == false && backer_needback == false{
-        x.project_status = ProjectStatus::Releasing; //releasing
-
-        //------add milestone votes in every milestone---------------
-        let mut milestone_votes = Vec::new();
-        for backer in x.backer_states.clone(){
-            milestone_votes.push(
-                Vote{ wallet: backer.backer_wallet, voted: false }
-            );
-        }
-        //-----add wefund vote------------------
-        let config = CONFIG.load(deps.storage)?;
-        milestone_votes.push(
-            Vote{ wallet: config.owner, voted: true}
-        );
+        transition_status(deps.storage, &env, &mut x, ProjectStatus::Releasing)?;
 
-        for i in 0..(x.milestone_states.len() as usize){
-            x.milestone_states[i].milestone_votes = milestone_votes.clone();
-        }
+        let config = CONFIG.load(deps.storage)?;
 
         if config.vesting_contract != "".to_string() && x.token_addr != "".to_string() {
             let vesting = x.vesting.clone();
@@ -1031,42 +2234,70 @@ pub fn try_back2project(
         }
     }
 
-    PROJECTSTATES.update(deps.storage, project_id.u128().into(), |op| match op {
+    //----------strategy fixed at first backing, config changes don't move escrow------
+    if x.yield_strategy.is_none() {
+        x.yield_strategy = Some(config.yield_strategy.clone());
+    }
+
+    //----------escrow the backed principal and the shares it buys-------------
+    let strategy = project_strategy(&config, &x);
+    let adapter = get_adapter(&strategy);
+    add_asset(&mut x.backed_assets, &fund_real_back);
+    if fund_real_back.info.is_uusd() {
+        x.escrow_principal += fund_real_back.amount;
+        x.escrow_shares += adapter.deposit_shares(&deps.querier, fund_real_back.amount.u128())?;
+    } else { //held by contract, only uusd goes to yield strategy
+        add_asset(&mut x.held_assets, &fund_real_back);
+    }
+
+    projects().update(deps.storage, project_id.u128().into(), |op| match op {
         None => Err(ContractError::NotRegisteredProject {}),
         Some(mut project) => {
             project.project_status = x.project_status.clone();
+            project.escrow_principal = x.escrow_principal;
+            project.escrow_shares = x.escrow_shares;
+            project.backed_assets = x.backed_assets.clone();
+            project.held_assets = x.held_assets.clone();
             project.communitybacked_amount = x.communitybacked_amount;
             project.backerbacked_amount = x.backerbacked_amount;
-            project.backer_states = x.backer_states;
-            project.communitybacker_states = x.communitybacker_states;
-            
-            if x.project_status == ProjectStatus::Releasing{//only on switching releasing status
-                project.milestone_states = x.milestone_states;
-            }
+            project.communitybacker_count = x.communitybacker_count;
+            project.backer_count = x.backer_count;
+            project.fundraising_stage = x.fundraising_stage;
+            project.fundraising_rounds = x.fundraising_rounds.clone();
+            project.stage_allocated = x.stage_allocated.clone();
+            project.yield_strategy = x.yield_strategy.clone();
             Ok(project)
         }
     })?;
 
-    //----------load config and read anchor market address-----------------
-    let config = CONFIG.load(deps.storage).unwrap();
-    let anchormarket = config.anchor_market;
+    //------open voting on first milestone---------------
+    if x.project_status == ProjectStatus::Releasing {
+        open_milestonevote(deps.storage, &x, &env)?;
+    }
 
-    //----------deposite to anchor market------------------------
-    let deposite_project = WasmMsg::Execute {
-            contract_addr: String::from(anchormarket),
-            msg: to_binary(&AnchorMarket::DepositStable {}).unwrap(),
-            funds: vec![fund_real_back]
-    };
-    msgs.push(CosmosMsg::Wasm(deposite_project));
+    //----------deposit with project yield strategy-----------------
+    if fund_real_back.info.is_uusd() {
+        msgs.extend(adapter.deposit(Coin::new(fund_real_back.amount.u128(), "uusd"))?);
+    }
 
-    //---------send to Wefund with 5/105--------------------
-    let bank_wefund = BankMsg::Send { 
-        to_address: config.wefund.to_string(),
-        amount: vec![fund_wefund] 
-    };
-    msgs.push(CosmosMsg::Bank(bank_wefund));
+    //---------retained part of fee stays until TransferAllCoins--------------
+    if retained > Uint128::zero() {
+        let mut fees = ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default();
+        add_asset(&mut fees, &Asset{ info: fund.info.clone(), amount: retained });
+        ACCRUED_FEES.save(deps.storage, &fees)?;
+    }
+
+    //---------send fee to wefund and split recipients--------------------
+    for (recipient, amount) in payouts.iter() {
+        msgs.push(Asset{ info: fund.info.clone(), amount: *amount }.transfer_msg(recipient)?);
+    }
+
+    //---------return what was over the cap--------------------
+    if excess > Uint128::zero() {
+        msgs.push(Asset{ info: fund.info.clone(), amount: excess }.transfer_msg(&sender)?);
+    }
 
-    let x = PROJECTSTATES.load(deps.storage, project_id.u128().into())?;
+    let x = projects().load(deps.storage, project_id.u128().into())?;
     if config.vesting_contract != "".to_string() && x.token_addr != "".to_string() {
         //----------add fundraising user------------------------
         let add_fundraising_user = WasmMsg::Execute {
@@ -1074,7 +2305,7 @@ pub fn try_back2project(
             msg: to_binary(
                 &VestingMsg::AddUser {
                     project_id: x.project_id,
-                    wallet: info.sender,
+                    wallet: backer_wallet,
                     stage: fundraising_stage,
                     amount: token_amount,
                 }
@@ -1087,5 +2318,8 @@ pub fn try_back2project(
     Ok(Response::new()
     .add_messages(msgs)
     .add_attribute("action", "back to project")
+    .add_attribute("backed", fund_real_back.amount.to_string())
+    .add_attribute("returned", excess.to_string())
+    .add_attribute("token_amount", token_amount.to_string())
     )
 }
@@ -11,6 +11,15 @@ pub enum ContractError {
     #[error("Unauthorized")]
     Unauthorized {},
 
+    #[error("Yield strategy reports zero exchange rate")]
+    ZeroExchangeRate {},
+
+    #[error("No accrued fees to transfer")]
+    NoAccruedFees {},
+
+    #[error("Cannot migrate from {contract}")]
+    CannotMigrate { contract: String },
+
     #[error("Project id is already registerd")]
     AlreadyRegisteredProject {},
 
@@ -20,12 +29,121 @@ pub enum ContractError {
     #[error("Need some coin")]
     NeedCoin{},
 
+    #[error("Send exactly one coin")]
+    MultipleDenoms{},
+
+    #[error("Asset not accepted by project : {asset}")]
+    NotAcceptedAsset{
+        asset: String,
+    },
+
+    #[error("Asset needs a rate and at most 18 decimals, uusd is always accepted : {asset}")]
+    InvalidAssetRate{
+        asset: String,
+    },
+
     #[error("Alreay enough collected")]
     AlreadyCollected{},
 
+    #[error("Wallet backing below project minimum : {min}")]
+    BelowWalletMin{
+        min: Uint128,
+    },
+
+    #[error("Wallet already backed project maximum")]
+    WalletLimitReached{},
+
+    #[error("Invalid wallet limits")]
+    InvalidWalletLimits{},
+
+    #[error("Invalid fundraising rounds")]
+    InvalidFundraisingRounds{},
+
+    #[error("Not active fundraising round, active : {stage}")]
+    NotActiveRound{
+        stage: Uint128,
+    },
+
+    #[error("Fundraising round starts at : {start}")]
+    RoundNotStarted{
+        start: Uint128,
+    },
+
+    #[error("No fundraising round left")]
+    NoActiveRound{},
+
+    #[error("Stage price must be above zero")]
+    InvalidStagePrice{},
+
+    #[error("Tokens of stage sold out : {stage}")]
+    StageSoldOut{
+        stage: Uint128,
+    },
+
+    #[error("Send exactly the submit deposit : {deposit}uusd")]
+    WrongDeposit{
+        deposit: Uint128,
+    },
+
+    #[error("Project is under review")]
+    UnderReview{},
+
+    #[error("Not correct review status")]
+    NotCorrectReviewStatus{},
+
+    #[error("Not eligible to vote on listing")]
+    NotEligibleVoter{},
+
+    #[error("Invalid listing voting rules")]
+    InvalidListingVoting{},
+
     #[error("Alreay done or failed")]
     AlreadyDoneFail{},
 
+    #[error("Project already has backed funds")]
+    AlreadyBacked{},
+
+    #[error("Nothing left in project escrow")]
+    EmptyEscrow{},
+
+    #[error("Project escrow not paid out yet")]
+    EscrowNotEmpty{},
+
+    #[error("Fundraising deadline passed")]
+    FundraisingExpired{},
+
+    #[error("Fundraising deadline not passed yet")]
+    NotExpired{},
+
+    #[error("Nothing to refund")]
+    NothingToRefund{},
+
+    #[error("Milestone voting is not open")]
+    VotingClosed{},
+
+    #[error("Milestone voting not ended yet")]
+    VotingNotEnded{},
+
+    #[error("Invalid milestone voting rules")]
+    InvalidMilestoneVoting{},
+
+    #[error("Invalid fee config")]
+    InvalidFeeConfig{},
+
+    #[error("Invalid project metadata : {field}")]
+    InvalidMetadata{ field: String },
+
+    #[error("No config change queued")]
+    NoPendingConfig{},
+
+    #[error("Timelock not expired, change executable after {execute_after}")]
+    TimelockNotExpired{
+        execute_after: Uint128,
+    },
+
+    #[error("No ownership transfer proposed")]
+    NoPendingOwner{},
+
     #[error("Invalid Address")]
     InvalidAddress{},
 
@@ -35,9 +153,15 @@ pub enum ContractError {
     #[error("Not registered community member")]
     NotRegisteredCommunity{},
 
-    #[error("Not correct status : {status}")]
+    #[error("Not correct status : {status:?}")]
     NotCorrectStatus{
-        status: u32,
+        status: ProjectStatus,
+    },
+
+    #[error("Invalid status transition : {from:?} to {to:?}")]
+    InvalidTransition{
+        from: ProjectStatus,
+        to: ProjectStatus,
     },
 
     #[error("Alreay voted")]
@@ -0,0 +1,253 @@
+use cosmwasm_std::{Addr, Api, StdResult, Storage, Uint128};
+
+use crate::error::ContractError;
+use crate::state::{FeeConfig, FeeSplit, FEE_CONFIG};
+
+pub const BPS: u128 = 10000; //basis points in one
+
+//------------how one contribution is split----------------------------
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fee{
+    pub bps: Uint128, //rate applied
+    pub backed: Uint128, //goes to project escrow
+    pub fee: Uint128,
+    pub retained: Uint128, //stays in contract
+    pub payouts: Vec<(Addr, Uint128)>,
+}
+
+//------------fee config, default one for contracts before it existed--------
+pub fn load_fee_config(store: &dyn Storage) -> StdResult<FeeConfig>
+{
+    Ok(FEE_CONFIG.may_load(store)?.unwrap_or_default())
+}
+
+//------------rate of the highest tier `amount` reaches------------------
+pub fn fee_bps(fee_config: &FeeConfig, amount: Uint128) -> Uint128
+{
+    fee_config.tiers.iter()
+        .filter(|tier| amount >= tier.min_amount)
+        .max_by_key(|tier| tier.min_amount)
+        .map(|tier| tier.fee_bps)
+        .unwrap_or(fee_config.fee_bps)
+}
+
+//------------`fee_override` is the project rate, `community` backers get the discount-----
+pub fn calc_fee(fee_config: &FeeConfig, wefund: &Addr, amount: Uint128,
+    fee_override: Option<Uint128>, community: bool)
+    -> Result<Fee, ContractError>
+{
+    if amount < fee_config.min_contribution {
+        return Err(ContractError::NeedCoin{});
+    }
+
+    //--------fee is charged on top, backed + fee = amount-----------------
+    let bps = fee_override.unwrap_or_else(|| fee_bps(fee_config, amount));
+    let mut fee = amount - amount.multiply_ratio(BPS, BPS + bps.u128());
+    if fee < fee_config.flat_fee {
+        fee = fee_config.flat_fee;
+    }
+    if community {
+        fee -= fee.multiply_ratio(fee_config.community_discount_bps, BPS);
+    }
+    if fee >= amount {
+        return Err(ContractError::NeedCoin{});
+    }
+
+    Ok(split_fee(fee_config, wefund, bps, amount - fee, fee))
+}
+
+//------------fee when no more than `cap` can be backed, returns part to give back-----
+pub fn calc_capped_fee(fee_config: &FeeConfig, wefund: &Addr, amount: Uint128,
+    fee_override: Option<Uint128>, community: bool, cap: Uint128)
+    -> Result<(Fee, Uint128), ContractError>
+{
+    let fee = calc_fee(fee_config, wefund, amount, fee_override, community)?;
+    if fee.backed <= cap {
+        return Ok((fee, Uint128::zero()));
+    }
+
+    //--------rate of whole amount charged on top of the capped part only----------
+    let mut capped_fee = cap.multiply_ratio(fee.bps, BPS);
+    if capped_fee < fee_config.flat_fee {
+        capped_fee = fee_config.flat_fee;
+    }
+    if community {
+        capped_fee -= capped_fee.multiply_ratio(fee_config.community_discount_bps, BPS);
+    }
+    let capped_fee = std::cmp::min(capped_fee, fee.fee);
+
+    Ok((
+        split_fee(fee_config, wefund, fee.bps, cap, capped_fee),
+        amount - cap - capped_fee,
+    ))
+}
+
+//------------retained part, then splits, rest to wefund-------------------
+fn split_fee(fee_config: &FeeConfig, wefund: &Addr, bps: Uint128, backed: Uint128, fee: Uint128)
+    -> Fee
+{
+    let retained = std::cmp::min(fee_config.retained, fee);
+    let payable = fee - retained;
+
+    let mut payouts: Vec<(Addr, Uint128)> = Vec::new();
+    let mut paid = Uint128::zero();
+    for split in fee_config.splits.iter() {
+        let share = payable.multiply_ratio(split.share_bps, BPS);
+        if share > Uint128::zero() {
+            payouts.push((split.recipient.clone(), share));
+            paid += share;
+        }
+    }
+    if payable > paid {
+        payouts.push((wefund.clone(), payable - paid));
+    }
+
+    Fee{
+        bps,
+        backed,
+        fee,
+        retained,
+        payouts,
+    }
+}
+
+pub fn validate_fee_config(api: &dyn Api, fee_config: FeeConfig)
+    -> Result<FeeConfig, ContractError>
+{
+    let max = Uint128::new(BPS);
+    if fee_config.fee_bps > max
+        || fee_config.tiers.iter().any(|tier| tier.fee_bps > max)
+        || fee_config.community_discount_bps > max
+        || fee_config.min_contribution <= fee_config.flat_fee
+    {
+        return Err(ContractError::InvalidFeeConfig{});
+    }
+
+    let mut total = Uint128::zero();
+    let mut splits: Vec<FeeSplit> = Vec::new();
+    for split in fee_config.splits.iter() {
+        total += split.share_bps;
+        splits.push(FeeSplit{
+            recipient: api.addr_validate(split.recipient.as_str())?,
+            share_bps: split.share_bps,
+        });
+    }
+    if total > max {
+        return Err(ContractError::InvalidFeeConfig{});
+    }
+
+    Ok(FeeConfig{ splits, ..fee_config })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::testing::MockApi;
+    use crate::state::FeeTier;
+
+    fn wefund() -> Addr {
+        Addr::unchecked("wefund")
+    }
+
+    #[test]
+    fn tier_boundaries(){
+        let fee_config = FeeConfig{
+            tiers: vec![FeeTier{ min_amount: Uint128::new(1000000000), fee_bps: Uint128::new(250) }],
+            ..FeeConfig::default()
+        };
+    //tier starts at its min amount
+        assert_eq!(fee_bps(&fee_config, Uint128::new(999999999)), Uint128::new(500));
+        assert_eq!(fee_bps(&fee_config, Uint128::new(1000000000)), Uint128::new(250));
+    //flat fee up to 105 UST, rate above it
+        let fee_config = FeeConfig::default();
+        let fee = calc_fee(&fee_config, &wefund(), Uint128::new(105000000), None, false).unwrap();
+        assert_eq!(fee.fee, Uint128::new(5000000));
+        let fee = calc_fee(&fee_config, &wefund(), Uint128::new(105000021), None, false).unwrap();
+        assert_eq!(fee.fee, Uint128::new(5000001));
+        assert_eq!(fee.backed, Uint128::new(100000020));
+    //min contribution is inclusive
+        assert!(calc_fee(&fee_config, &wefund(), Uint128::new(5999999), None, false).is_err());
+        assert!(calc_fee(&fee_config, &wefund(), Uint128::new(6000000), None, false).is_ok());
+    }
+
+    #[test]
+    fn cap_below_flat_fee(){
+        let fee_config = FeeConfig::default();
+    //rate on 1 UST is below flat fee, flat fee charged, rest returned
+        let (fee, excess) = calc_capped_fee(&fee_config, &wefund(), Uint128::new(210000000),
+            None, false, Uint128::new(1000000)).unwrap();
+        assert_eq!(fee.backed, Uint128::new(1000000));
+        assert_eq!(fee.fee, Uint128::new(5000000));
+        assert_eq!(fee.retained, Uint128::new(4000000));
+        assert_eq!(fee.payouts, vec![(wefund(), Uint128::new(1000000))]);
+        assert_eq!(excess, Uint128::new(204000000));
+    //never more than the uncapped fee
+        let (fee, excess) = calc_capped_fee(&fee_config, &wefund(), Uint128::new(10000000),
+            None, false, Uint128::new(1000000)).unwrap();
+        assert_eq!(fee.fee, Uint128::new(5000000));
+        assert_eq!(excess, Uint128::new(4000000));
+    //nothing returned when cap is not reached
+        let (fee, excess) = calc_capped_fee(&fee_config, &wefund(), Uint128::new(105000000),
+            None, false, Uint128::new(100000000)).unwrap();
+        assert_eq!(fee.backed, Uint128::new(100000000));
+        assert_eq!(excess, Uint128::zero());
+    }
+
+    #[test]
+    fn split_remainders(){
+        let split = |recipient: &str| FeeSplit{
+            recipient: Addr::unchecked(recipient),
+            share_bps: Uint128::new(3333),
+        };
+        let fee_config = FeeConfig{
+            retained: Uint128::zero(),
+            splits: vec![split("a"), split("b"), split("c")],
+            ..FeeConfig::default()
+        };
+    //rounding left over goes to wefund
+        let fee = calc_fee(&fee_config, &wefund(), Uint128::new(210000000), None, false).unwrap();
+        assert_eq!(fee.payouts, vec![
+            (Addr::unchecked("a"), Uint128::new(3333000)),
+            (Addr::unchecked("b"), Uint128::new(3333000)),
+            (Addr::unchecked("c"), Uint128::new(3333000)),
+            (wefund(), Uint128::new(1000)),
+        ]);
+    //retained takes whole fee when it is larger, nothing paid out
+        let fee_config = FeeConfig{
+            retained: Uint128::new(20000000),
+            ..fee_config
+        };
+        let fee = calc_fee(&fee_config, &wefund(), Uint128::new(210000000), None, false).unwrap();
+        assert_eq!(fee.retained, Uint128::new(10000000));
+        assert!(fee.payouts.is_empty());
+    //full community discount waives fee
+        let fee_config = FeeConfig{
+            community_discount_bps: Uint128::new(10000),
+            ..FeeConfig::default()
+        };
+        let fee = calc_fee(&fee_config, &wefund(), Uint128::new(210000000), None, true).unwrap();
+        assert_eq!(fee.fee, Uint128::zero());
+        assert_eq!(fee.backed, Uint128::new(210000000));
+        assert!(fee.payouts.is_empty());
+    }
+
+    #[test]
+    fn fee_config_validation(){
+        let api = MockApi::default();
+        assert!(validate_fee_config(&api, FeeConfig::default()).is_ok());
+    //flat fee must leave something to back
+        let fee_config = FeeConfig{ min_contribution: Uint128::new(5000000), ..FeeConfig::default() };
+        assert!(matches!(validate_fee_config(&api, fee_config), Err(ContractError::InvalidFeeConfig{})));
+    //rates and splits at most 100%
+        let fee_config = FeeConfig{ fee_bps: Uint128::new(10001), ..FeeConfig::default() };
+        assert!(validate_fee_config(&api, fee_config).is_err());
+        let split = |share_bps: u128| FeeSplit{
+            recipient: Addr::unchecked("partner"),
+            share_bps: Uint128::new(share_bps),
+        };
+        let fee_config = FeeConfig{ splits: vec![split(5000), split(5001)], ..FeeConfig::default() };
+        assert!(validate_fee_config(&api, fee_config).is_err());
+        let fee_config = FeeConfig{ splits: vec![split(5000), split(5000)], ..FeeConfig::default() };
+        assert!(validate_fee_config(&api, fee_config).is_ok());
+    }
+}
@@ -0,0 +1,316 @@
+use cosmwasm_std::{Addr, Coin, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::{Item, Map, U128Key};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use crate::asset::{Asset, AssetInfo, add_asset};
+use crate::state::{BackerState, Config, Metadata, Milestone, MilestoneVoting, ProjectMetadata,
+    ProjectState, ProjectStatus, TeamMember, VestingParameter, Vote, YieldStrategy, projects,
+    CONFIG, MILESTONES, TEAMMEMBERS, backers, Contribution, CONTRIBUTIONS, FeeConfig, FEE_CONFIG,
+    DEFAULT_TIMELOCK, DEFAULT_QUORUM, DEFAULT_THRESHOLD, DEFAULT_VOTE_PERIOD, DEFAULT_MAX_REJECTIONS};
+use crate::yield_adapter::UST;
+
+//--------------Config before yield strategies and timelock--------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub owner: Addr,
+    pub wefund: Addr,
+    pub anchor_market: Addr,
+    pub aust_token: Addr,
+    pub vesting_contract: Addr,
+}
+
+pub const LEGACY_CONFIG: Item<LegacyConfig> = Item::new("config");
+
+//--------------BackerState, one per backing, kept inline in project---------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyBackerState{
+    pub backer_wallet: Addr,
+    pub ust_amount: Coin,
+    pub aust_amount: Coin,
+    pub otherchain: String,
+    pub otherchain_wallet: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyVote{
+    pub wallet: Addr,
+    pub voted: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyMilestone{
+    pub milestone_step: Uint128,
+    pub milestone_name: String,
+    pub milestone_description: String,
+    pub milestone_startdate: String,
+    pub milestone_enddate: String,
+    pub milestone_amount: Uint128,
+    pub milestone_status: Uint128, //0:voting, 1:releasing 2:released
+    pub milestone_votes: Vec<LegacyVote>,
+}
+
+//--------------ProjectState with loose meta data and everything inline---------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyProjectState{
+    pub project_company: String,
+    pub project_title: String,
+    pub project_description: String,
+    pub project_ecosystem: String,
+    pub project_createddate: String,
+    pub project_saft: String,
+    pub project_logo: String,
+    pub project_whitepaper: String,
+    pub project_website: String,
+    pub project_email: String,
+    pub project_id: Uint128,
+    pub creator_wallet: Addr,
+    pub project_collected: Uint128,
+    pub project_status: ProjectStatus,
+    pub fundraising_stage: Uint128,
+    pub backerbacked_amount: Uint128,
+    pub communitybacked_amount: Uint128,
+    pub backer_states: Vec<LegacyBackerState>,
+    pub communitybacker_states: Vec<LegacyBackerState>,
+    pub milestone_states: Vec<LegacyMilestone>,
+    pub project_milestonestep: Uint128,
+    pub teammember_states: Vec<TeamMember>,
+    pub vesting: Vec<VestingParameter>,
+    pub token_addr: Addr,
+}
+
+pub const LEGACY_PROJECTSTATES: Map<U128Key, LegacyProjectState> = Map::new("prj");
+
+//-----------anchor addresses become the yield strategy, fee config seeded-------
+pub fn migrate_config(store: &mut dyn Storage) -> StdResult<Config>
+{
+    let legacy = LEGACY_CONFIG.load(store)?;
+    let config = Config{
+        owner: legacy.owner,
+        wefund: legacy.wefund,
+        yield_strategy: YieldStrategy::Anchor{
+            market: legacy.anchor_market,
+            aust_token: legacy.aust_token,
+        },
+        vesting_contract: legacy.vesting_contract,
+        timelock: Uint128::new(DEFAULT_TIMELOCK),
+        accepted_assets: Vec::new(),
+    };
+    CONFIG.save(store, &config)?;
+    if FEE_CONFIG.may_load(store)?.is_none() {
+        FEE_CONFIG.save(store, &FeeConfig::default())?;
+    }
+    Ok(config)
+}
+
+//-----------fee old back2project charged on `backed` ust, net of fee---------
+fn legacy_fee(backed: Uint128) -> Uint128
+{
+    //5/105 from 100 UST sent on, 5 UST below
+    if backed.u128() * 105 >= 100 * UST * 100 {
+        backed.multiply_ratio(5u128, 100u128)
+    } else {
+        Uint128::new(5 * UST)
+    }
+}
+
+//-----------principal still parked: backed less milestones released so far------
+fn legacy_unreleased(project: &LegacyProjectState) -> Uint128
+{
+    let released: u128 = project.milestone_states.iter()
+        .take(project.project_milestonestep.u128() as usize)
+        .map(|milestone| milestone.milestone_amount.u128() * UST)
+        .sum();
+    (project.backerbacked_amount + project.communitybacked_amount)
+        .saturating_sub(Uint128::new(released))
+}
+
+fn legacy_principal(project: &LegacyProjectState) -> Uint128
+{
+    match project.project_status {
+        ProjectStatus::Fundraising | ProjectStatus::Releasing => legacy_unreleased(project),
+        _ => Uint128::zero(), //paid out on done, redeemed on fail
+    }
+}
+
+//-----------turn every old project into the current layout------------------
+//aust held by contract is split over projects by principal still parked
+pub fn migrate_projects(store: &mut dyn Storage, config: &Config, aust_balance: Uint128)
+    -> StdResult<Vec<ProjectState>>
+{
+    let legacy: Vec<LegacyProjectState> = LEGACY_PROJECTSTATES
+        .range(store, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, project)| project))
+        .collect::<StdResult<_>>()?;
+
+    let total_principal: Uint128 = legacy.iter().map(legacy_principal).sum();
+
+    let mut migrated = Vec::new();
+    for project in legacy {
+        let id = project.project_id;
+        let failed = project.project_status == ProjectStatus::Fail;
+        let mut backer_count = Uint128::zero();
+        let mut communitybacker_count = Uint128::zero();
+        let total_backed = project.backerbacked_amount + project.communitybacked_amount;
+        let unreleased = legacy_unreleased(&project);
+        let mut held = Uint128::zero();
+
+        //-------one record per wallet, backings of a wallet summed up--------
+        let community = project.communitybacker_states.iter().map(|backer| (backer, true));
+        let backer = project.backer_states.iter().map(|backer| (backer, false));
+        for (old, is_community) in community.chain(backer) {
+            let key = (U128Key::new(id.u128()), old.backer_wallet.clone());
+            let backer = backers().may_load(store, key.clone())?;
+            if backer.is_none() {
+                if is_community {
+                    communitybacker_count += Uint128::new(1);
+                } else {
+                    backer_count += Uint128::new(1);
+                }
+            }
+            let mut backer = backer.unwrap_or(BackerState{
+                project_id: id,
+                backer_wallet: old.backer_wallet.clone(),
+                community: is_community,
+                ust_amount: Coin::new(0, "uusd"),
+                aust_amount: Coin::new(0, "aust"),
+                backed_assets: Vec::new(),
+                otherchain: String::new(),
+                otherchain_wallet: String::new(),
+                //old fail refunded backers right away, community share stayed as uusd
+                refunded: failed && !is_community,
+                refund_shares: Uint128::zero(),
+                refund_assets: Vec::new(),
+                fee_bps: Uint128::new(500),
+                fee_amount: Uint128::zero(),
+                token_amount: Uint128::zero(),
+                delegate: None,
+            });
+            let backed = Asset{ info: AssetInfo::uusd(), amount: old.ust_amount.amount };
+            let fee_amount = legacy_fee(old.ust_amount.amount);
+            backer.ust_amount.amount += old.ust_amount.amount;
+            backer.aust_amount.amount += old.aust_amount.amount;
+            add_asset(&mut backer.backed_assets, &backed);
+            backer.otherchain = old.otherchain.clone();
+            backer.otherchain_wallet = old.otherchain_wallet.clone();
+            backer.fee_amount += fee_amount;
+            if failed && is_community && !total_backed.is_zero() {
+                let refund = old.ust_amount.amount.multiply_ratio(unreleased, total_backed);
+                add_asset(&mut backer.refund_assets, &Asset{ info: AssetInfo::uusd(), amount: refund });
+                held += refund;
+            }
+            backers().save(store, key.clone(), &backer)?;
+
+            //-------time of old backings was not kept------------
+            let mut history = CONTRIBUTIONS.may_load(store, key.clone())?.unwrap_or_default();
+            history.push(Contribution{
+                asset: backed,
+                fee_amount,
+                fundraising_stage: project.fundraising_stage,
+                token_amount: Uint128::zero(),
+                timestamp: Uint128::zero(),
+            });
+            CONTRIBUTIONS.save(store, key, &history)?;
+        }
+
+        //-------votes keep what was cast, weight is backed ust of wallet---------
+        for (step, old) in project.milestone_states.iter().enumerate() {
+            let mut votes = Vec::new();
+            for vote in old.milestone_votes.iter() {
+                let weight = backers().may_load(store, (id.u128().into(), vote.wallet.clone()))?
+                    .map_or(Uint128::zero(), |backer| backer.ust_amount.amount);
+                votes.push(Vote{
+                    wallet: vote.wallet.clone(),
+                    voted: if vote.voted { Some(true) } else { None },
+                    weight,
+                    delegate: None,
+                });
+            }
+            MILESTONES.save(store, (id.u128().into(), (step as u128).into()), &Milestone{
+                milestone_step: old.milestone_step,
+                milestone_name: old.milestone_name.clone(),
+                milestone_description: old.milestone_description.clone(),
+                milestone_startdate: old.milestone_startdate.clone(),
+                milestone_enddate: old.milestone_enddate.clone(),
+                milestone_amount: old.milestone_amount,
+                milestone_status: old.milestone_status,
+                milestone_votes: votes,
+                milestone_evidence: Vec::new(),
+                milestone_rejections: Uint128::zero(),
+                milestone_votestart: Uint128::zero(),
+                milestone_voteend: Uint128::zero(),
+            })?;
+        }
+        TEAMMEMBERS.save(store, id.u128().into(), &project.teammember_states)?;
+
+        let escrow_principal = legacy_principal(&project);
+        let escrow_shares = if total_principal.is_zero() {
+            Uint128::zero()
+        } else {
+            aust_balance.multiply_ratio(escrow_principal, total_principal)
+        };
+
+        let x = ProjectState{
+            metadata: Metadata::OnChain(ProjectMetadata{
+                company: project.project_company,
+                title: project.project_title,
+                description: project.project_description,
+                ecosystem: project.project_ecosystem,
+                //kept only when it was stored as seconds
+                created_at: project.project_createddate.parse::<u128>()
+                    .map(Uint128::new).unwrap_or_default(),
+                saft: project.project_saft,
+                logo: project.project_logo,
+                whitepaper: project.project_whitepaper,
+                website: project.project_website,
+                email: project.project_email,
+                socials: Vec::new(),
+            }),
+            project_id: id,
+            creator_wallet: project.creator_wallet,
+            project_collected: project.project_collected,
+            project_status: project.project_status,
+            fundraising_stage: project.fundraising_stage,
+            fundraising_deadline: None,
+            fundraising_rounds: Vec::new(),
+            backerbacked_amount: project.backerbacked_amount,
+            communitybacked_amount: project.communitybacked_amount,
+            backer_count,
+            communitybacker_count,
+            wallet_min_amount: None,
+            wallet_max_amount: None,
+            accepted_assets: vec![AssetInfo::uusd()],
+            backed_assets: if total_backed.is_zero() {
+                Vec::new()
+            } else {
+                vec![Asset{ info: AssetInfo::uusd(), amount: total_backed }]
+            },
+            held_assets: if held.is_zero() {
+                Vec::new()
+            } else {
+                vec![Asset{ info: AssetInfo::uusd(), amount: held }]
+            },
+            milestone_count: Uint128::new(project.milestone_states.len() as u128),
+            project_milestonestep: project.project_milestonestep,
+            milestone_voting: MilestoneVoting{
+                quorum: Uint128::new(DEFAULT_QUORUM),
+                threshold: Uint128::new(DEFAULT_THRESHOLD),
+                vote_period: Uint128::new(DEFAULT_VOTE_PERIOD),
+                max_rejections: Uint128::new(DEFAULT_MAX_REJECTIONS),
+            },
+            vesting: project.vesting,
+            stage_allocated: Vec::new(),
+            token_addr: project.token_addr,
+            yield_strategy: Some(config.yield_strategy.clone()), //old escrow is all aust
+            fee_bps: None,
+            escrow_principal,
+            escrow_shares,
+        };
+
+        //-------old record can't be read as ProjectState, drop before indexing-------
+        LEGACY_PROJECTSTATES.remove(store, id.u128().into());
+        projects().save(store, id.u128().into(), &x)?;
+        migrated.push(x);
+    }
+    Ok(migrated)
+}
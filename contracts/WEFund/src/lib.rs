@@ -4,11 +4,20 @@ mod error;
 pub mod msg;
 pub mod state;
 pub mod market;
+pub mod vault;
+pub mod yield_adapter;
+pub mod util;
+pub mod asset;
+pub mod fee;
+pub mod metadata;
+pub mod legacy;
+pub mod staking;
+pub mod vote_weight;
 
 pub use crate::error::ContractError;
 
 #[cfg(test)]
 mod testing;
 
-// #[cfg(test)]
-// mod mock_querier;
+#[cfg(test)]
+mod mock_querier;
@@ -0,0 +1,199 @@
+use cosmwasm_std::{to_vec, StdResult, Uint128};
+use sha2::{Digest, Sha256};
+
+use crate::error::ContractError;
+use crate::msg::{MetadataMsg, ProjectMetadataMsg};
+use crate::state::{Metadata, MetadataRef, ProjectMetadata};
+
+pub const MAX_NAME_LEN: usize = 64;
+pub const MAX_DESCRIPTION_LEN: usize = 2048;
+pub const MAX_URL_LEN: usize = 256;
+pub const MAX_EMAIL_LEN: usize = 128;
+pub const MAX_SOCIALS: usize = 10;
+
+fn invalid(field: &str) -> ContractError
+{
+    ContractError::InvalidMetadata{ field: field.to_string() }
+}
+
+fn check_len(field: &str, value: &str, max: usize, required: bool)
+    -> Result<(), ContractError>
+{
+    if value.len() > max || (required && value.trim().is_empty()) {
+        return Err(invalid(field));
+    }
+    Ok(())
+}
+
+//------------http(s) link with a host, no whitespace-------------------
+pub fn is_valid_url(url: &str) -> bool
+{
+    let rest = match url.strip_prefix("https://").or_else(|| url.strip_prefix("http://")) {
+        Some(rest) => rest,
+        None => return false,
+    };
+    let host = rest.split(&['/', '?', '#'][..]).next().unwrap_or("");
+    url.len() <= MAX_URL_LEN
+        && !host.is_empty()
+        && !url.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+pub fn is_valid_email(email: &str) -> bool
+{
+    let mut parts = email.split('@');
+    let (local, domain) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(local), Some(domain), None) => (local, domain),
+        _ => return false,
+    };
+    email.len() <= MAX_EMAIL_LEN
+        && !local.is_empty()
+        && domain.contains('.')
+        && !domain.starts_with('.')
+        && !domain.ends_with('.')
+        && !email.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+//------------content address like ipfs://<cid>--------------------------
+pub fn is_valid_ipfs(uri: &str) -> bool
+{
+    match uri.strip_prefix("ipfs://") {
+        Some(cid) => uri.len() <= MAX_URL_LEN
+            && !cid.is_empty()
+            && cid.chars().all(|c| c.is_ascii_alphanumeric() || c == '/' || c == '.' || c == '-' || c == '_'),
+        None => false,
+    }
+}
+
+pub fn sha256_hex(data: &[u8]) -> String
+{
+    hex::encode(Sha256::digest(data))
+}
+
+//------------url field that may be left empty----------------------------
+fn check_url(field: &str, url: &str, required: bool) -> Result<(), ContractError>
+{
+    if url.is_empty() && !required {
+        return Ok(());
+    }
+    if !is_valid_url(url) {
+        return Err(invalid(field));
+    }
+    Ok(())
+}
+
+pub fn validate_metadata(msg: ProjectMetadataMsg, created_at: Uint128)
+    -> Result<ProjectMetadata, ContractError>
+{
+    check_len("company", &msg.company, MAX_NAME_LEN, true)?;
+    check_len("title", &msg.title, MAX_NAME_LEN, true)?;
+    check_len("description", &msg.description, MAX_DESCRIPTION_LEN, true)?;
+    check_len("ecosystem", &msg.ecosystem, MAX_NAME_LEN, false)?;
+
+    check_url("website", &msg.website, true)?;
+    check_url("logo", &msg.logo, false)?;
+    check_url("whitepaper", &msg.whitepaper, false)?;
+    check_url("saft", &msg.saft, false)?;
+
+    if !is_valid_email(&msg.email) {
+        return Err(invalid("email"));
+    }
+
+    if msg.socials.len() > MAX_SOCIALS {
+        return Err(invalid("socials"));
+    }
+    for social in msg.socials.iter() {
+        check_len("socials", &social.name, MAX_NAME_LEN, true)?;
+        check_url("socials", &social.url, true)?;
+    }
+
+    Ok(ProjectMetadata{
+        company: msg.company,
+        title: msg.title,
+        description: msg.description,
+        ecosystem: msg.ecosystem,
+        created_at,
+        saft: msg.saft,
+        logo: msg.logo,
+        whitepaper: msg.whitepaper,
+        website: msg.website,
+        email: msg.email,
+        socials: msg.socials,
+    })
+}
+
+pub fn validate_metadata_msg(msg: MetadataMsg, created_at: Uint128)
+    -> Result<Metadata, ContractError>
+{
+    match msg {
+        MetadataMsg::OnChain(msg) => Ok(Metadata::OnChain(validate_metadata(msg, created_at)?)),
+        MetadataMsg::OffChain{ title, sha256, uri } => {
+            check_len("title", &title, MAX_NAME_LEN, true)?;
+
+            let sha256 = sha256.to_lowercase();
+            if sha256.len() != 64 || !sha256.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(invalid("sha256"));
+            }
+            if !uri.is_empty() && !is_valid_url(&uri) && !is_valid_ipfs(&uri) {
+                return Err(invalid("uri"));
+            }
+
+            Ok(Metadata::OffChain(MetadataRef{ title, created_at, sha256, uri }))
+        }
+    }
+}
+
+//------------sha256 of the blob, json of meta data when kept on chain----------
+pub fn metadata_hash(metadata: &Metadata) -> StdResult<String>
+{
+    match metadata {
+        Metadata::OnChain(metadata) => Ok(sha256_hex(&to_vec(metadata)?)),
+        Metadata::OffChain(metadata) => Ok(metadata.sha256.clone()),
+    }
+}
+
+//------------names of fields `new` changes-------------------------------
+pub fn changed_fields(old: &Metadata, new: &Metadata) -> Vec<&'static str>
+{
+    match (old, new) {
+        (Metadata::OnChain(old), Metadata::OnChain(new)) => changed_onchain_fields(old, new),
+        (Metadata::OffChain(old), Metadata::OffChain(new)) => {
+            let mut fields = Vec::new();
+            if old.title != new.title {
+                fields.push("title");
+            }
+            if old.sha256 != new.sha256 {
+                fields.push("sha256");
+            }
+            if old.uri != new.uri {
+                fields.push("uri");
+            }
+            fields
+        }
+        _ => vec!["storage"],
+    }
+}
+
+fn changed_onchain_fields(old: &ProjectMetadata, new: &ProjectMetadata) -> Vec<&'static str>
+{
+    let mut fields = Vec::new();
+    let pairs: [(&'static str, &String, &String); 9] = [
+        ("company", &old.company, &new.company),
+        ("title", &old.title, &new.title),
+        ("description", &old.description, &new.description),
+        ("ecosystem", &old.ecosystem, &new.ecosystem),
+        ("saft", &old.saft, &new.saft),
+        ("logo", &old.logo, &new.logo),
+        ("whitepaper", &old.whitepaper, &new.whitepaper),
+        ("website", &old.website, &new.website),
+        ("email", &old.email, &new.email),
+    ];
+    for (field, old, new) in pairs.iter() {
+        if old != new {
+            fields.push(*field);
+        }
+    }
+    if old.socials != new.socials {
+        fields.push("socials");
+    }
+    fields
+}
@@ -1,17 +1,16 @@
-use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
-
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage, MOCK_CONTRACT_ADDR};
 use cosmwasm_std::{
-    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    from_binary, from_slice, to_binary, Coin, ContractResult, Decimal, Empty, OwnedDeps, Querier,
     QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
-use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg};
+use cw20::{BalanceResponse as Cw20BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use cosmwasm_bignumber::{Decimal256, Uint256};
 
-use std::collections::HashMap;
+use crate::market::{EpochStateResponse, QueryMsg as MarketQueryMsg};
+use crate::vault::{ExchangeRateResponse, QueryMsg as VaultQueryMsg};
+use crate::staking::QueryMsg as StakingQueryMsg;
 
-use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
-use terraswap::asset::{AssetInfo, PairInfo};
+use std::collections::HashMap;
 
 /// mock_dependencies is a drop-in replacement for cosmwasm_std::testing::mock_dependencies
 /// this uses our CustomQuerier.
@@ -29,22 +28,25 @@ pub fn mock_dependencies(
 }
 
 pub struct WasmMockQuerier {
-    base: MockQuerier<TerraQueryWrapper>,
+    base: MockQuerier<Empty>,
     token_querier: TokenQuerier,
-    tax_querier: TaxQuerier,
-    terraswap_factory_querier: TerraswapFactoryQuerier,
+    exchange_rate: Decimal256, //aust to uusd of money market
+    vault_rate: Decimal, //share token to uusd of vault
+    stakes: HashMap<String, Vec<(u64, Uint128)>>, //wallet to (height changed, staked amount)
 }
 
 #[derive(Clone, Default)]
 pub struct TokenQuerier {
     // this lets us iterate over all pairs that match the first string
     balances: HashMap<String, HashMap<String, Uint128>>,
+    decimals: HashMap<String, u8>,
 }
 
 impl TokenQuerier {
     pub fn new(balances: &[(&String, &[(&String, &Uint128)])]) -> Self {
         TokenQuerier {
             balances: balances_to_map(balances),
+            decimals: HashMap::new(),
         }
     }
 }
@@ -64,55 +66,9 @@ pub(crate) fn balances_to_map(
     balances_map
 }
 
-#[derive(Clone, Default)]
-pub struct TaxQuerier {
-    rate: Decimal,
-    // this lets us iterate over all pairs that match the first string
-    caps: HashMap<String, Uint128>,
-}
-
-impl TaxQuerier {
-    pub fn new(rate: Decimal, caps: &[(&String, &Uint128)]) -> Self {
-        TaxQuerier {
-            rate,
-            caps: caps_to_map(caps),
-        }
-    }
-}
-
-pub(crate) fn caps_to_map(caps: &[(&String, &Uint128)]) -> HashMap<String, Uint128> {
-    let mut owner_map: HashMap<String, Uint128> = HashMap::new();
-    for (denom, cap) in caps.iter() {
-        owner_map.insert(denom.to_string(), **cap);
-    }
-    owner_map
-}
-
-#[derive(Clone, Default)]
-pub struct TerraswapFactoryQuerier {
-    pairs: HashMap<String, String>,
-}
-
-impl TerraswapFactoryQuerier {
-    pub fn new(pairs: &[(&String, &String)]) -> Self {
-        TerraswapFactoryQuerier {
-            pairs: pairs_to_map(pairs),
-        }
-    }
-}
-
-pub(crate) fn pairs_to_map(pairs: &[(&String, &String)]) -> HashMap<String, String> {
-    let mut pairs_map: HashMap<String, String> = HashMap::new();
-    for (key, pair) in pairs.iter() {
-        pairs_map.insert(key.to_string(), pair.to_string());
-    }
-    pairs_map
-}
-
 impl Querier for WasmMockQuerier {
     fn raw_query(&self, bin_request: &[u8]) -> QuerierResult {
-        // MockQuerier doesn't support Custom, so we ignore it completely here
-        let request: QueryRequest<TerraQueryWrapper> = match from_slice(bin_request) {
+        let request: QueryRequest<Empty> = match from_slice(bin_request) {
             Ok(v) => v,
             Err(e) => {
                 return SystemResult::Err(SystemError::InvalidRequest {
@@ -125,96 +81,81 @@ impl Querier for WasmMockQuerier {
     }
 }
 
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
-#[serde(rename_all = "snake_case")]
-pub enum QueryMsg {
-    Pair { asset_infos: [AssetInfo; 2] },
-}
-
 impl WasmMockQuerier {
-    pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
+    pub fn handle_query(&self, request: &QueryRequest<Empty>) -> QuerierResult {
         match &request {
-            QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
-                if route == &TerraRoute::Treasury {
-                    match query_data {
-                        TerraQuery::TaxRate {} => {
-                            let res = TaxRateResponse {
-                                rate: self.tax_querier.rate,
-                            };
-                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
-                        }
-                        TerraQuery::TaxCap { denom } => {
-                            let cap = self
-                                .tax_querier
-                                .caps
-                                .get(denom)
-                                .copied()
-                                .unwrap_or_default();
-                            let res = TaxCapResponse { cap };
-                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
-                        }
-                        _ => panic!("DO NOT ENTER HERE"),
-                    }
-                } else {
-                    panic!("DO NOT ENTER HERE")
-                }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) 
+                if matches!(from_binary(msg), Ok(MarketQueryMsg::EpochState{ .. })) => 
+            {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&EpochStateResponse {
+                        exchange_rate: self.exchange_rate,
+                        aterra_supply: Uint256::zero(),
+                    })
+                    .unwrap(),
+                ))
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) 
+                if matches!(from_binary(msg), Ok(VaultQueryMsg::ExchangeRate{})) => 
+            {
+                SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&ExchangeRateResponse {
+                        exchange_rate: self.vault_rate,
+                    })
+                    .unwrap(),
+                ))
             }
-            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg) {
-                Ok(QueryMsg::Pair { asset_infos }) => {
-                    let key = asset_infos[0].to_string() + asset_infos[1].to_string().as_str();
-                    match self.terraswap_factory_querier.pairs.get(&key) {
-                        Some(v) => SystemResult::Ok(ContractResult::from(to_binary(&PairInfo {
-                            contract_addr: v.to_string(),
-                            liquidity_token: "liquidity".to_string(),
-                            asset_infos: [
-                                AssetInfo::NativeToken {
-                                    denom: "uusd".to_string(),
-                                },
-                                AssetInfo::NativeToken {
-                                    denom: "uusd".to_string(),
-                                },
-                            ],
-                        }))),
-                        None => SystemResult::Err(SystemError::InvalidRequest {
-                            error: "No pair info exists".to_string(),
-                            request: msg.as_slice().into(),
-                        }),
-                    }
+            QueryRequest::Wasm(WasmQuery::Smart { msg, .. }) 
+                if matches!(from_binary(msg), Ok(StakingQueryMsg::GetStakeAt{ .. })
+                    | Ok(StakingQueryMsg::GetTotalStakeAt{ .. })) => 
+            {
+                let stake = match from_binary(msg).unwrap() {
+                    StakingQueryMsg::GetStakeAt{ wallet, height } => self.stake_at(wallet.as_str(), height),
+                    StakingQueryMsg::GetTotalStakeAt{ height } => self.stakes.keys()
+                        .map(|wallet| self.stake_at(wallet, height))
+                        .sum(),
+                };
+                SystemResult::Ok(ContractResult::Ok(to_binary(&stake).unwrap()))
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => match from_binary(msg).unwrap() {
+                Cw20QueryMsg::Balance { address } => {
+                    let balance = self
+                        .token_querier
+                        .balances
+                        .get(contract_addr)
+                        .and_then(|balances| balances.get(&address))
+                        .copied()
+                        .unwrap_or_default();
+
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&Cw20BalanceResponse { balance }).unwrap(),
+                    ))
+                }
+                Cw20QueryMsg::TokenInfo {} => {
+                    let decimals = match self.token_querier.decimals.get(contract_addr) {
+                        Some(decimals) => *decimals,
+                        None => {
+                            return SystemResult::Err(SystemError::InvalidRequest {
+                                error: format!(
+                                    "No token info exists for the contract {}",
+                                    contract_addr
+                                ),
+                                request: msg.as_slice().into(),
+                            })
+                        }
+                    };
+
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&TokenInfoResponse {
+                            name: contract_addr.to_string(),
+                            symbol: "TKN".to_string(),
+                            decimals,
+                            total_supply: Uint128::zero(),
+                        })
+                        .unwrap(),
+                    ))
                 }
-                _ => match from_binary(msg).unwrap() {
-                    Cw20QueryMsg::Balance { address } => {
-                        let balances: &HashMap<String, Uint128> =
-                            match self.token_querier.balances.get(contract_addr) {
-                                Some(balances) => balances,
-                                None => {
-                                    return SystemResult::Err(SystemError::InvalidRequest {
-                                        error: format!(
-                                            "No balance info exists for the contract {}",
-                                            contract_addr
-                                        ),
-                                        request: msg.as_slice().into(),
-                                    })
-                                }
-                            };
-
-                        let balance = match balances.get(&address) {
-                            Some(v) => *v,
-                            None => {
-                                return SystemResult::Ok(ContractResult::Ok(
-                                    to_binary(&Cw20BalanceResponse {
-                                        balance: Uint128::zero(),
-                                    })
-                                    .unwrap(),
-                                ));
-                            }
-                        };
-
-                        SystemResult::Ok(ContractResult::Ok(
-                            to_binary(&Cw20BalanceResponse { balance }).unwrap(),
-                        ))
-                    }
-                    _ => panic!("DO NOT ENTER HERE"),
-                },
+                _ => panic!("DO NOT ENTER HERE"),
             },
             _ => self.base.handle_query(request),
         }
@@ -222,27 +163,45 @@ impl WasmMockQuerier {
 }
 
 impl WasmMockQuerier {
-    pub fn new(base: MockQuerier<TerraQueryWrapper>) -> Self {
+    pub fn new(base: MockQuerier<Empty>) -> Self {
         WasmMockQuerier {
             base,
             token_querier: TokenQuerier::default(),
-            tax_querier: TaxQuerier::default(),
-            terraswap_factory_querier: TerraswapFactoryQuerier::default(),
+            exchange_rate: Decimal256::one(),
+            vault_rate: Decimal::one(),
+            stakes: HashMap::new(),
         }
     }
 
+    fn stake_at(&self, wallet: &str, height: u64) -> Uint128 {
+        self.stakes.get(wallet)
+            .and_then(|stakes| stakes.iter().rev().find(|(changed, _)| *changed < height))
+            .map(|(_, amount)| *amount)
+            .unwrap_or_default()
+    }
+
     // configure the mint whitelist mock querier
     pub fn with_token_balances(&mut self, balances: &[(&String, &[(&String, &Uint128)])]) {
         self.token_querier = TokenQuerier::new(balances);
     }
 
-    // configure the token owner mock querier
-    pub fn with_tax(&mut self, rate: Decimal, caps: &[(&String, &Uint128)]) {
-        self.tax_querier = TaxQuerier::new(rate, caps);
+    // configure the token info mock querier
+    pub fn with_token_decimals(&mut self, token: &String, decimals: u8) {
+        self.token_querier.decimals.insert(token.to_string(), decimals);
+    }
+
+    // configure the money market exchange rate
+    pub fn with_exchange_rate(&mut self, exchange_rate: Decimal256) {
+        self.exchange_rate = exchange_rate;
+    }
+
+    // configure the vault exchange rate
+    pub fn with_vault_rate(&mut self, vault_rate: Decimal) {
+        self.vault_rate = vault_rate;
     }
 
-    // configure the terraswap pair
-    pub fn with_terraswap_pairs(&mut self, pairs: &[(&String, &String)]) {
-        self.terraswap_factory_querier = TerraswapFactoryQuerier::new(pairs);
+    // configure the stake of wallet as changed in block `height`, seen by snapshots after it
+    pub fn with_stake(&mut self, wallet: &str, height: u64, amount: Uint128) {
+        self.stakes.entry(wallet.to_string()).or_default().push((height, amount));
     }
 }
@@ -1,46 +1,83 @@
-use cosmwasm_std::{Uint128};
+use cosmwasm_std::{Uint128, Addr, Binary};
+use cw20::Cw20ReceiveMsg;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use crate::state::{Milestone, TeamMember, VestingParameter};
+use crate::asset::{Asset, AssetInfo, AssetRate};
+use Vesting::msg::UserInfo;
+use crate::state::{Milestone, TeamMember, VestingParameter, YieldStrategy, MilestoneVoting, Vote,
+    Role, FeeConfig, SocialLink, ProjectState, BackerState, Contribution, ProjectStatus, ListingVoting,
+    FundraisingRound};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub admin: Option<String>,
     pub wefund: Option<String>,
-    pub anchor_market: Option<String>,
-    pub aust_token: Option<String>,
-    pub vesting_contract: Option<String>
+    pub yield_strategy: Option<YieldStrategy>,
+    pub vesting_contract: Option<String>,
+    pub timelock: Option<Uint128>
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+//------------what reviewer decides on a submitted project--------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ReviewDecision {
+    RequestChanges,
+    Approve,
+    Reject{ slash: bool }, //slash deposit of spam submissions
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    SetConfig { admin:Option<String>,  wefund: Option<String>, 
-        anchor_market: Option<String>, aust_token:Option<String> , 
-        vesting_contract:Option<String>},
+    SetConfig { wefund: Option<String>, 
+        yield_strategy: Option<YieldStrategy>, 
+        vesting_contract:Option<String>,
+        timelock: Option<Uint128>},
+    ExecuteConfig{},
+    CancelConfig{},
+
+    ProposeOwner{owner: String},
+    AcceptOwnership{},
+    CancelOwnershipProposal{},
+
     AddProject { 
-        project_company: String,
-        project_title: String,
-        project_description: String,
-        project_ecosystem: String,
-        project_createddate: String,
-        project_saft: String,
-        project_logo: String,
-        project_whitepaper: String,
-        project_website: String,
-        project_email: String,
+        project_metadata: MetadataMsg,
         creator_wallet: String,
         project_collected: Uint128,
         project_milestones: Vec<Milestone>,
         project_teammembers: Vec<TeamMember>,
         vesting: Vec<VestingParameter>,
-        token_addr: String
+        token_addr: String,
+        fundraising_deadline: Option<Uint128>,
+        milestone_voting: Option<MilestoneVoting>,
+        accepted_assets: Option<Vec<AssetInfo>>
     },
     RemoveProject{project_id: Uint128 },
+    SubmitProject { 
+        project_metadata: MetadataMsg,
+        project_collected: Uint128,
+        project_milestones: Vec<Milestone>,
+        project_teammembers: Vec<TeamMember>,
+        vesting: Vec<VestingParameter>,
+        token_addr: String,
+        fundraising_deadline: Option<Uint128>,
+        milestone_voting: Option<MilestoneVoting>,
+        accepted_assets: Option<Vec<AssetInfo>>
+    },
+    ReviewProject{project_id: Uint128, decision: ReviewDecision, comment: String, fee_bps: Option<Uint128>},
+    ResubmitProject{project_id: Uint128, comment: String},
+    SetSubmitDeposit{amount: Uint128},
+    CastListingVote{project_id: Uint128, voted: bool},
+    TallyListingVote{project_id: Uint128},
+    SetListingVoting{listing_voting: ListingVoting},
+    UpdateProjectMetadata{project_id: Uint128, project_metadata: MetadataMsg},
 
     Back2Project { project_id: Uint128, backer_wallet: String, 
-        fundraising_stage: Uint128, token_amount: Uint128, 
+        fundraising_stage: Uint128, 
         otherchain:String, otherchain_wallet:String},
+    Receive(Cw20ReceiveMsg),
 
     CompleteProject{ project_id: Uint128 },
     FailProject{project_id: Uint128 },
@@ -50,24 +87,263 @@ pub enum ExecuteMsg {
     AddCommunitymember{wallet: String},
     RemoveCommunitymember{wallet: String},
 
-    WefundApprove{project_id:Uint128},
+    WefundApprove{project_id:Uint128, fee_bps:Option<Uint128>},
     SetFundraisingStage{project_id: Uint128, stage: Uint128},
+    SetWalletLimits{project_id: Uint128, min_amount: Option<Uint128>, max_amount: Option<Uint128>},
+    SetFundraisingRounds{project_id: Uint128, rounds: Vec<FundraisingRound>},
     
-    SetMilestoneVote{project_id: Uint128, wallet:String, voted: bool},
+    SetMilestoneVote{project_id: Uint128, voted: bool, on_behalf_of: Option<String>},
+    DelegateVote{project_id: Uint128, delegate: Option<String>},
+    TallyMilestone{project_id: Uint128},
+    ResubmitMilestone{project_id: Uint128, description: String, evidence: Vec<String>},
 
     ReleaseMilestone{project_id: Uint128},
 
-    SetProjectStatus{project_id: Uint128, status: Uint128}
+    SetProjectStatus{project_id: Uint128, status: ProjectStatus},
+
+    SetProjectYieldStrategy{project_id: Uint128, yield_strategy: Option<YieldStrategy>},
+
+    ExpireProject{project_id: Uint128},
+    ClaimRefund{project_id: Uint128},
+
+    GrantRole{wallet: String, role: Role},
+    RevokeRole{wallet: String, role: Role},
+
+    SetFeeConfig{fee_config: FeeConfig},
+    SetAcceptedAssets{assets: Vec<AssetRate>},
+}
+
+//------------project fields shared by AddProject and SubmitProject------------
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProjectParams {
+    pub project_metadata: MetadataMsg,
+    pub project_collected: Uint128,
+    pub project_milestones: Vec<Milestone>,
+    pub project_teammembers: Vec<TeamMember>,
+    pub vesting: Vec<VestingParameter>,
+    pub token_addr: String,
+    pub fundraising_deadline: Option<Uint128>,
+    pub milestone_voting: Option<MilestoneVoting>,
+    pub accepted_assets: Option<Vec<AssetInfo>>,
+}
+
+//------------backing fields shared by Back2Project and its cw20 hook------------
+#[derive(Clone, Debug, PartialEq)]
+pub struct BackParams {
+    pub project_id: Uint128,
+    pub backer_wallet: String,
+    pub fundraising_stage: Uint128,
+    pub otherchain: String,
+    pub otherchain_wallet: String,
+}
+
+//------------project meta data, created_at is taken from block time-------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProjectMetadataMsg {
+    pub company: String,
+    pub title: String,
+    pub description: String,
+    pub ecosystem: String,
+    pub saft: String,
+    pub logo: String,
+    pub whitepaper: String,
+    pub website: String,
+    pub email: String,
+    pub socials: Vec<SocialLink>,
+}
+
+//------------full meta data on chain, or only a title and sha256 of the blob------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataMsg {
+    OnChain(ProjectMetadataMsg),
+    OffChain{ title: String, sha256: String, uri: String },
+}
+
+//------------hook of cw20 Send to this contract------------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    Back2Project { project_id: Uint128, backer_wallet: String, 
+        fundraising_stage: Uint128, 
+        otherchain:String, otherchain_wallet:String},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     GetConfig{},
+    GetPendingConfig{},
+    GetPendingOwner{},
     GetAllProject{},
+    ListProjects{
+        start_after: Option<Uint128>,
+        limit: Option<u32>,
+        status: Option<ProjectStatus>,
+        ecosystem: Option<String>,
+        creator: Option<String>,
+    },
     GetProject { project_id:Uint128 },
     GetBacker{ project_id:Uint128},
+    ListBackers{
+        project_id: Uint128,
+        kind: BackerKind,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    GetBackerPosition{ project_id:Uint128, wallet:String },
+    GetWalletPortfolio{ wallet:String },
+    GetReview{ project_id:Uint128 },
+    ListPendingReviews{ start_after:Option<Uint128>, limit:Option<u32> },
+    GetSubmitDeposit{},
+    GetListingVote{ project_id:Uint128 },
+    GetListingVoting{},
     GetBalance{ wallet:String },
     GetCommunitymembers{},
+    GetProjectEscrow{ project_id:Uint128 },
+    GetRefundable{ project_id:Uint128, wallet:String },
+    GetMilestoneVotes{ project_id:Uint128, step:Option<Uint128> },
+    GetContributions{ project_id:Uint128, wallet:String },
+    GetProjectHistory{ project_id:Uint128 },
+    GetFundraisingRound{ project_id:Uint128 },
+    GetRoles{ wallet:String },
+    GetFeeConfig{},
+    GetAccruedFees{},
+    GetProjectMetadataHash{ project_id:Uint128 },
+    VerifyMetadata{ project_id:Uint128, blob:Binary },
+}
+
+//------------which half of backers to list-------------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum BackerKind {
+    All,
+    Community,
+    Public,
+}
+
+//------------page of backers and totals per kind---------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BackersResponse {
+    pub project_id: Uint128,
+    pub backers: Vec<BackerState>,
+    pub backer_count: Uint128,
+    pub backerbacked_amount: Uint128,
+    pub communitybacker_count: Uint128,
+    pub communitybacked_amount: Uint128,
+}
+
+//------------position of a wallet in one project---------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PortfolioPosition {
+    pub project_id: Uint128,
+    pub title: String,
+    pub project_status: ProjectStatus,
+    pub community: bool,
+    pub principal: Uint128, //backed at par, net of fee
+    pub backed_assets: Vec<Asset>,
+    pub refundable: RefundableResponse,
+    pub token_amount: Uint128, //allocated on backing
+    pub vesting: Option<UserInfo>, //None unless project vests through vesting contract
+    pub can_vote: bool, //wallet has a vote on current milestone
+    pub pending_vote: Option<Uint128>, //milestone step open and not voted yet
+}
+
+//------------every project a wallet backed---------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PortfolioResponse {
+    pub wallet: Addr,
+    pub positions: Vec<PortfolioPosition>,
+}
+
+//------------lightweight project entry of ListProjects---------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProjectSummary {
+    pub project_id: Uint128,
+    pub title: String,
+    pub ecosystem: Option<String>, //None when meta data is off chain
+    pub creator_wallet: Addr,
+    pub project_status: ProjectStatus,
+    pub project_collected: Uint128,
+    pub backed_amount: Uint128, //backer and community halves together
+    pub fundraising_deadline: Option<Uint128>,
+    pub milestone_count: Uint128,
+    pub project_milestonestep: Uint128,
+}
+
+//------------project with its backers, milestones and team---------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProjectResponse {
+    pub project: ProjectState,
+    pub backer_states: Vec<BackerState>,
+    pub communitybacker_states: Vec<BackerState>,
+    pub milestone_states: Vec<Milestone>,
+    pub teammember_states: Vec<TeamMember>,
+}
+
+//------------per project escrow and yield it earned---------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EscrowResponse {
+    pub project_id: Uint128,
+    pub principal: Uint128,
+    pub shares: Uint128,
+    pub exchange_rate: Uint128, //uusd per share, scaled by 1000000
+    pub value: Uint128,
+    pub accrued_yield: Uint128,
 }
 
+//------------refund a wallet can claim from a failed project------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RefundableResponse {
+    pub project_id: Uint128,
+    pub wallet: Addr,
+    pub shares: Uint128,
+    pub amount: Uint128, //uusd the shares are worth now
+    pub assets: Vec<Asset>, //other backed assets held by contract
+}
+
+
+//------------votes on a milestone, current one if step not given---------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MilestoneVotesResponse {
+    pub project_id: Uint128,
+    pub step: Uint128,
+    pub milestone_status: Uint128,
+    pub votestart: Uint128,
+    pub voteend: Uint128,
+    pub votes: Vec<Vote>,
+}
+
+//------------round taking contributions now, None once all rounds are over---------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundraisingRoundResponse {
+    pub project_id: Uint128,
+    pub stage: Uint128,
+    pub stage_title: String,
+    pub stage_price: Uint128,
+    pub round: Option<FundraisingRound>,
+}
+
+//------------aggregated position of a wallet and its contributions---------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ContributionsResponse {
+    pub project_id: Uint128,
+    pub wallet: Addr,
+    pub position: BackerState,
+    pub contributions: Vec<Contribution>,
+}
+
+//------------hash of project meta data, of its json when kept on chain---------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MetadataHashResponse {
+    pub project_id: Uint128,
+    pub title: String,
+    pub on_chain: bool,
+    pub sha256: String,
+    pub uri: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VerifyMetadataResponse {
+    pub project_id: Uint128,
+    pub valid: bool,
+}
@@ -1,14 +1,29 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    Addr, to_binary, Binary, Deps, Env, StdResult,
+    Addr, to_binary, Binary, Deps, Env, StdResult, StdError,
     Uint128, QueryRequest, BankQuery,
-    Coin, AllBalanceResponse
+    Coin, AllBalanceResponse, Order
 };
-use cw20::{Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse};
+use cw_storage_plus::Bound;
 
-use crate::msg::{QueryMsg};
-use crate::state::{Config, CONFIG, PROJECTSTATES, ProjectState, BackerState, COMMUNITY};
+use crate::msg::{QueryMsg, EscrowResponse, RefundableResponse, MilestoneVotesResponse,
+    MetadataHashResponse, VerifyMetadataResponse, ProjectResponse, ContributionsResponse,
+    ProjectSummary, BackerKind, BackersResponse, PortfolioPosition, PortfolioResponse,
+    FundraisingRoundResponse};
+use Vesting::msg::{QueryMsg as VestingQueryMsg, UserInfo};
+use crate::yield_adapter::{UST, get_adapter, project_strategy};
+use crate::asset::Asset;
+use crate::state::{Config, CONFIG, ACCRUED_FEES, projects, ProjectState, BackerState, COMMUNITY, ROLES, Role,
+    PENDING_CONFIG, PENDING_OWNER, Metadata, MILESTONES, TEAMMEMBERS, backers, load_backers,
+    load_milestones, load_milestone, CONTRIBUTIONS, ProjectStatus, reviews, Review, ReviewStatus,
+    SUBMIT_DEPOSIT, DEFAULT_SUBMIT_DEPOSIT, LISTING_VOTES, LISTING_VOTING, PROJECT_HISTORY, active_round};
+use crate::fee::load_fee_config;
+use crate::metadata::{metadata_hash, sha256_hex};
+
+//------------page size of list queries-----------------
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
 
 // version info for migration info
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -16,11 +31,164 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::GetBalance{ wallet } => to_binary(&query_balance(deps, _env, wallet)?),
         QueryMsg::GetConfig{ } => to_binary(&query_getconfig(deps)?),
+        QueryMsg::GetPendingConfig{ } => to_binary(&PENDING_CONFIG.may_load(deps.storage)?),
+        QueryMsg::GetPendingOwner{ } => to_binary(&PENDING_OWNER.may_load(deps.storage)?),
         QueryMsg::GetAllProject{ } => to_binary(&query_allproject(deps)?),
+        QueryMsg::ListProjects{ start_after, limit, status, ecosystem, creator } => 
+            to_binary(&query_listprojects(deps, start_after, limit, status, ecosystem, creator)?),
         QueryMsg::GetProject{ project_id } => to_binary(&query_project(deps, project_id)?),
         QueryMsg::GetBacker{ project_id } => to_binary(&query_backer(deps, project_id)?),
+        QueryMsg::ListBackers{ project_id, kind, start_after, limit } => 
+            to_binary(&query_listbackers(deps, project_id, kind, start_after, limit)?),
+        QueryMsg::GetBackerPosition{ project_id, wallet } => to_binary(&query_backerposition(deps, project_id, wallet)?),
+        QueryMsg::GetWalletPortfolio{ wallet } => to_binary(&query_walletportfolio(deps, _env, wallet)?),
+        QueryMsg::GetReview{ project_id } => to_binary(&reviews().may_load(deps.storage, project_id.u128().into())?),
+        QueryMsg::ListPendingReviews{ start_after, limit } => to_binary(&query_pendingreviews(deps, start_after, limit)?),
+        QueryMsg::GetListingVote{ project_id } => to_binary(&LISTING_VOTES.may_load(deps.storage, project_id.u128().into())?),
+        QueryMsg::GetListingVoting{ } => to_binary(&LISTING_VOTING.may_load(deps.storage)?.unwrap_or_default()),
+        QueryMsg::GetSubmitDeposit{ } => to_binary(&SUBMIT_DEPOSIT.may_load(deps.storage)?
+            .unwrap_or(Uint128::new(DEFAULT_SUBMIT_DEPOSIT))),
         QueryMsg::GetCommunitymembers{ } => to_binary(&query_communitymembers(deps)?),
+        QueryMsg::GetProjectEscrow{ project_id } => to_binary(&query_projectescrow(deps, project_id)?),
+        QueryMsg::GetRefundable{ project_id, wallet } => to_binary(&query_refundable(deps, project_id, wallet)?),
+        QueryMsg::GetMilestoneVotes{ project_id, step } => to_binary(&query_milestonevotes(deps, project_id, step)?),
+        QueryMsg::GetContributions{ project_id, wallet } => to_binary(&query_contributions(deps, project_id, wallet)?),
+        QueryMsg::GetFundraisingRound{ project_id } => to_binary(&query_fundraisinground(deps, _env, project_id)?),
+        QueryMsg::GetProjectHistory{ project_id } => to_binary(&PROJECT_HISTORY
+            .may_load(deps.storage, project_id.u128().into())?.unwrap_or_default()),
+        QueryMsg::GetRoles{ wallet } => to_binary(&query_roles(deps, wallet)?),
+        QueryMsg::GetFeeConfig{} => to_binary(&load_fee_config(deps.storage)?),
+        QueryMsg::GetAccruedFees{} => to_binary(&ACCRUED_FEES.may_load(deps.storage)?.unwrap_or_default()),
+        QueryMsg::GetProjectMetadataHash{ project_id } => to_binary(&query_metadatahash(deps, project_id)?),
+        QueryMsg::VerifyMetadata{ project_id, blob } => to_binary(&query_verifymetadata(deps, project_id, blob)?),
+    }
+}
+
+fn query_projectescrow(deps:Deps, id:Uint128) -> StdResult<EscrowResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+    let config = CONFIG.load(deps.storage)?;
+
+    let exchange_rate = get_adapter(&project_strategy(&config, &x)).exchange_rate(&deps.querier)?;
+    let value = Uint128::new(x.escrow_shares.u128() * exchange_rate / UST);
+
+    Ok(EscrowResponse{
+        project_id: id,
+        principal: x.escrow_principal,
+        shares: x.escrow_shares,
+        exchange_rate: Uint128::new(exchange_rate),
+        value,
+        accrued_yield: value.saturating_sub(x.escrow_principal),
+    })
+}
+
+fn query_refundable(deps:Deps, id:Uint128, wallet:String) -> StdResult<RefundableResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+    let wallet = deps.api.addr_validate(&wallet)?;
+
+    let mut shares = Uint128::zero();
+    let mut assets: Vec<Asset> = Vec::new();
+    if let Some(backer) = backers().may_load(deps.storage, (id.u128().into(), wallet.clone()))? {
+        if !backer.refunded {
+            shares = backer.refund_shares;
+            assets = backer.refund_assets;
+        }
     }
+
+    let config = CONFIG.load(deps.storage)?;
+    let exchange_rate = get_adapter(&project_strategy(&config, &x)).exchange_rate(&deps.querier)?;
+
+    Ok(RefundableResponse{
+        project_id: id,
+        wallet,
+        shares,
+        amount: Uint128::new(shares.u128() * exchange_rate / UST),
+        assets,
+    })
+}
+
+fn query_milestonevotes(deps:Deps, id:Uint128, step:Option<Uint128>) -> StdResult<MilestoneVotesResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+
+    let step = step.unwrap_or(x.project_milestonestep);
+    let milestone = MILESTONES.may_load(deps.storage, (id.u128().into(), step.u128().into()))?
+        .ok_or_else(|| StdError::generic_err("Not found Milestone index"))?;
+
+    Ok(MilestoneVotesResponse{
+        project_id: id,
+        step,
+        milestone_status: milestone.milestone_status,
+        votestart: milestone.milestone_votestart,
+        voteend: milestone.milestone_voteend,
+        votes: milestone.milestone_votes,
+    })
+}
+
+fn query_contributions(deps:Deps, id:Uint128, wallet:String) -> StdResult<ContributionsResponse>{
+    let wallet = deps.api.addr_validate(&wallet)?;
+    let key = (id.u128().into(), wallet.clone());
+
+    let position = backers().may_load(deps.storage, key.clone())?
+        .ok_or_else(|| StdError::generic_err("Not found Backer"))?;
+
+    Ok(ContributionsResponse{
+        project_id: id,
+        wallet,
+        position,
+        contributions: CONTRIBUTIONS.may_load(deps.storage, key)?.unwrap_or_default(),
+    })
+}
+
+fn query_fundraisinground(deps:Deps, env:Env, id:Uint128) -> StdResult<FundraisingRoundResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+
+    //-------rounds ended or filled are skipped on next backing------------
+    let stage = if x.fundraising_rounds.is_empty() {
+        x.fundraising_stage
+    } else {
+        active_round(&x.fundraising_rounds, x.fundraising_stage, 
+            Uint128::from(env.block.time.seconds()))
+    };
+    let index = stage.u128() as usize;
+    let vesting = x.vesting.get(index);
+
+    Ok(FundraisingRoundResponse{
+        project_id: id,
+        stage,
+        stage_title: vesting.map(|param| param.stage_title.clone()).unwrap_or_default(),
+        stage_price: vesting.map_or(Uint128::zero(), |param| param.stage_price),
+        round: x.fundraising_rounds.get(index).cloned(),
+    })
+}
+
+fn query_metadatahash(deps:Deps, id:Uint128) -> StdResult<MetadataHashResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+
+    let uri = match &x.metadata {
+        Metadata::OnChain(_) => String::new(),
+        Metadata::OffChain(metadata) => metadata.uri.clone(),
+    };
+    Ok(MetadataHashResponse{
+        project_id: id,
+        title: x.metadata.title().to_string(),
+        on_chain: matches!(x.metadata, Metadata::OnChain(_)),
+        sha256: metadata_hash(&x.metadata)?,
+        uri,
+    })
+}
+
+fn query_verifymetadata(deps:Deps, id:Uint128, blob:Binary) -> StdResult<VerifyMetadataResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+
+    Ok(VerifyMetadataResponse{
+        project_id: id,
+        valid: sha256_hex(blob.as_slice()) == metadata_hash(&x.metadata)?,
+    })
+}
+
+fn query_roles(deps:Deps, wallet:String) -> StdResult<Vec<Role>>{
+    let wallet = deps.api.addr_validate(&wallet)?;
+    let roles = ROLES.may_load(deps.storage, wallet)?.unwrap_or_default();
+    Ok(roles)
 }
 
 fn query_communitymembers(deps:Deps) -> StdResult<Vec<Addr>>{
@@ -38,13 +206,12 @@ fn query_balance(deps:Deps, _env:Env, wallet:String) -> StdResult<AllBalanceResp
 
     let config = CONFIG.load(deps.storage).unwrap();
 
-    let aust_balance: Cw20BalanceResponse = deps.querier.query_wasm_smart(
-        config.aust_token,
-        &Cw20QueryMsg::Balance{
-            address: wallet,
-        }
-    )?;
-    balance.amount.push(Coin::new(aust_balance.balance.u128(), "aust"));
+    //--------yield share tokens held by wallet---------------------
+    let adapter = get_adapter(&config.yield_strategy);
+    if let Some(share_token) = adapter.share_token() {
+        let share_balance = adapter.share_balance(&deps.querier, &Addr::unchecked(wallet))?;
+        balance.amount.push(Coin::new(share_balance.u128(), share_token.to_string()));
+    }
 
     Ok(balance)
 }
@@ -53,7 +220,7 @@ fn query_getconfig(deps:Deps) -> StdResult<Config> {
     Ok(config)
 }
 fn query_allproject(deps:Deps) -> StdResult<Vec<ProjectState>> {
-    let all: StdResult<Vec<_>> = PROJECTSTATES.range(deps.storage, None, None, 
+    let all: StdResult<Vec<_>> = projects().range(deps.storage, None, None, 
         cosmwasm_std::Order::Ascending).collect();
     let all = all.unwrap();
 
@@ -63,12 +230,173 @@ fn query_allproject(deps:Deps) -> StdResult<Vec<ProjectState>> {
     }
     Ok(all_project)
 }
+fn query_listprojects(deps:Deps, start_after:Option<Uint128>, limit:Option<u32>,
+    status:Option<ProjectStatus>, ecosystem:Option<String>, creator:Option<String>)
+    -> StdResult<Vec<ProjectSummary>>
+{
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive_int(id.u128()));
+    let creator = match creator {
+        Some(creator) => Some(deps.api.addr_validate(&creator)?),
+        None => None,
+    };
+
+    //----------status index, or all projects in id order-------------
+    let iter = match status {
+        Some(status) => projects().idx.status.prefix(status.key())
+            .range(deps.storage, start, None, Order::Ascending),
+        None => projects().range(deps.storage, start, None, Order::Ascending),
+    };
+
+    iter.filter(|item| match item {
+            Ok((_, x)) => creator.as_ref().is_none_or(|creator| &x.creator_wallet == creator)
+                && ecosystem.as_ref().is_none_or(|ecosystem| x.metadata.ecosystem() == Some(ecosystem.as_str())),
+            Err(_) => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(_, x)| ProjectSummary{
+            project_id: x.project_id,
+            title: x.metadata.title().to_string(),
+            ecosystem: x.metadata.ecosystem().map(|ecosystem| ecosystem.to_string()),
+            creator_wallet: x.creator_wallet,
+            project_status: x.project_status,
+            project_collected: x.project_collected,
+            backed_amount: x.backerbacked_amount + x.communitybacked_amount,
+            fundraising_deadline: x.fundraising_deadline,
+            milestone_count: x.milestone_count,
+            project_milestonestep: x.project_milestonestep,
+        }))
+        .collect()
+}
 fn query_backer(deps:Deps, id:Uint128) -> StdResult<Vec<BackerState>>{
-    let x = PROJECTSTATES.load(deps.storage, id.u128().into())?;
-    Ok(x.backer_states)
+    projects().load(deps.storage, id.u128().into())?;
+    let mut backer_states = load_backers(deps.storage, id)?;
+    backer_states.retain(|backer| !backer.community);
+    Ok(backer_states)
 }
-fn query_project(deps:Deps, id:Uint128) -> StdResult<ProjectState>{
-    let x = PROJECTSTATES.load(deps.storage, id.u128().into())?;
-    
-    Ok(x)
+fn query_pendingreviews(deps:Deps, start_after:Option<Uint128>, limit:Option<u32>)
+    -> StdResult<Vec<Review>>
+{
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| Bound::exclusive_int(id.u128()));
+
+    reviews().idx.status
+        .prefix(ReviewStatus::Submitted.key())
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, review)| review))
+        .collect()
+}
+fn query_listbackers(deps:Deps, id:Uint128, kind:BackerKind, start_after:Option<String>, limit:Option<u32>)
+    -> StdResult<BackersResponse>
+{
+    let x = projects().load(deps.storage, id.u128().into())?;
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = match start_after {
+        Some(wallet) => Some(Bound::exclusive(deps.api.addr_validate(&wallet)?.as_bytes())),
+        None => None,
+    };
+
+    let backers = backers()
+        .prefix(id.u128().into())
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter(|item| match (item, &kind) {
+            (Ok((_, backer)), BackerKind::Community) => backer.community,
+            (Ok((_, backer)), BackerKind::Public) => !backer.community,
+            _ => true,
+        })
+        .take(limit)
+        .map(|item| item.map(|(_, backer)| backer))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(BackersResponse{
+        project_id: id,
+        backers,
+        backer_count: x.backer_count,
+        backerbacked_amount: x.backerbacked_amount,
+        communitybacker_count: x.communitybacker_count,
+        communitybacked_amount: x.communitybacked_amount,
+    })
+}
+fn query_backerposition(deps:Deps, id:Uint128, wallet:String) -> StdResult<Option<BackerState>>{
+    let wallet = deps.api.addr_validate(&wallet)?;
+    backers().may_load(deps.storage, (id.u128().into(), wallet))
+}
+fn query_walletportfolio(deps:Deps, env:Env, wallet:String) -> StdResult<PortfolioResponse>{
+    let wallet = deps.api.addr_validate(&wallet)?;
+    let config = CONFIG.load(deps.storage)?;
+    let now = Uint128::from(env.block.time.seconds());
+
+    //----------projects of wallet by backers wallet index-------------
+    let positions: Vec<BackerState> = backers().idx.wallet
+        .prefix(wallet.clone())
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, backer)| backer))
+        .collect::<StdResult<_>>()?;
+
+    let mut portfolio: Vec<PortfolioPosition> = Vec::new();
+    for backer in positions {
+        let x = projects().load(deps.storage, backer.project_id.u128().into())?;
+
+        //----------allocation vesting contract keeps for wallet------------
+        let vesting: Option<UserInfo> = if !config.vesting_contract.as_str().is_empty() && !x.token_addr.as_str().is_empty() {
+            deps.querier.query_wasm_smart(
+                config.vesting_contract.clone(),
+                &VestingQueryMsg::GetUserInfo{ project_id: x.project_id, wallet: wallet.to_string() }
+            ).ok()
+        } else {
+            None
+        };
+
+        //----------vote on current milestone--------------------
+        let mut can_vote = false;
+        let mut pending_vote = None;
+        if x.project_status == ProjectStatus::Releasing {
+            let milestone = load_milestone(deps.storage, x.project_id, x.project_milestonestep)?;
+            if let Some(vote) = milestone.milestone_votes.iter().find(|vote| vote.wallet == wallet) {
+                can_vote = true;
+                if milestone.milestone_status == Uint128::zero() 
+                    && vote.voted.is_none() && now <= milestone.milestone_voteend 
+                {
+                    pending_vote = Some(x.project_milestonestep);
+                }
+            }
+        }
+
+        portfolio.push(PortfolioPosition{
+            project_id: x.project_id,
+            title: x.metadata.title().to_string(),
+            project_status: x.project_status.clone(),
+            community: backer.community,
+            principal: backer.ust_amount.amount,
+            backed_assets: backer.backed_assets,
+            refundable: query_refundable(deps, x.project_id, wallet.to_string())?,
+            token_amount: backer.token_amount,
+            vesting,
+            can_vote,
+            pending_vote,
+        });
+    }
+
+    Ok(PortfolioResponse{
+        wallet,
+        positions: portfolio,
+    })
+}
+fn query_project(deps:Deps, id:Uint128) -> StdResult<ProjectResponse>{
+    let x = projects().load(deps.storage, id.u128().into())?;
+
+    //----------rebuild full project from its separate storage---------
+    let (communitybacker_states, backer_states) = load_backers(deps.storage, id)?
+        .into_iter()
+        .partition(|backer| backer.community);
+
+    Ok(ProjectResponse{
+        project: x,
+        backer_states,
+        communitybacker_states,
+        milestone_states: load_milestones(deps.storage, id)?,
+        teammember_states: TEAMMEMBERS.may_load(deps.storage, id.u128().into())?
+            .unwrap_or_default(),
+    })
 }
@@ -0,0 +1,13 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use cosmwasm_std::Addr;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QueryMsg {
+    /// Tokens `wallet` had staked at `height`, zero when it never staked
+    GetStakeAt { wallet: Addr, height: u64 },
+    /// Tokens staked by all wallets at `height`, rewards not included
+    GetTotalStakeAt { height: u64 },
+}
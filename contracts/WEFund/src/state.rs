@@ -1,34 +1,185 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Addr, Uint128, Coin, StdResult, Storage};
-use cw_storage_plus::{Item, Map, U128Key};
+use cosmwasm_std::{Addr, Uint128, Coin, Order, StdResult, Storage};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, U128Key};
+use crate::asset::{Asset, AssetInfo, AssetRate};
 //------------Config---------------------------------------
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub owner: Addr,
     pub wefund: Addr,
-    pub anchor_market: Addr,
-    pub aust_token: Addr,
+    pub yield_strategy: YieldStrategy,
     pub vesting_contract: Addr,
+    pub timelock: Uint128, //seconds a queued config change waits
+    pub accepted_assets: Vec<AssetRate>, //projects may accept these beside uusd
+}
+
+impl Config {
+    //------rate backing in `info` is valued at, None when not whitelisted-------
+    pub fn asset_rate(&self, info: &AssetInfo) -> Option<AssetRate> {
+        if info.is_uusd() {
+            return Some(AssetRate::uusd());
+        }
+        self.accepted_assets.iter().find(|rate| &rate.info == info).cloned()
+    }
 }
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const DEFAULT_TIMELOCK: u128 = 172800; //2 days
+
+//------------Config change waiting for timelock---------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingConfig {
+    pub wefund: Addr,
+    pub yield_strategy: YieldStrategy,
+    pub vesting_contract: Addr,
+    pub timelock: Uint128,
+    pub execute_after: Uint128, //seconds
+}
+
+pub const PENDING_CONFIG: Item<PendingConfig> = Item::new("pending_config");
 
-//-------------backer states---------------------------------
+//------------Platform fee charged on every backing--------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeTier {
+    pub min_amount: Uint128, //contribution the tier starts from
+    pub fee_bps: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeSplit {
+    pub recipient: Addr,
+    pub share_bps: Uint128, //part of the paid out fee, rest goes to config.wefund
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeConfig {
+    pub fee_bps: Uint128, //charged on top of backed amount, 500 takes 5/105
+    pub flat_fee: Uint128, //fee charged at least
+    pub min_contribution: Uint128,
+    pub retained: Uint128, //kept by contract out of every fee
+    pub reserve: Uint128, //uusd TransferAllCoins leaves in contract
+    pub community_discount_bps: Uint128, //off the fee of community backers, 10000 waives it
+    pub tiers: Vec<FeeTier>,
+    pub splits: Vec<FeeSplit>,
+}
+
+impl Default for FeeConfig {
+    fn default() -> Self {
+        FeeConfig {
+            fee_bps: Uint128::new(500),
+            flat_fee: Uint128::new(5000000), //5 UST
+            min_contribution: Uint128::new(6000000), //6 UST
+            retained: Uint128::new(4000000), //4 UST
+            reserve: Uint128::new(4000000), //4 UST
+            community_discount_bps: Uint128::zero(),
+            tiers: Vec::new(),
+            splits: Vec::new(),
+        }
+    }
+}
+
+pub const FEE_CONFIG: Item<FeeConfig> = Item::new("fee_config");
+
+//------------retained fees not swept yet, the only funds TransferAllCoins moves------
+pub const ACCRUED_FEES: Item<Vec<Asset>> = Item::new("accrued_fees");
+
+//------------Owner proposed, waiting to accept----------------
+pub const PENDING_OWNER: Item<Addr> = Item::new("pending_owner");
+
+//------------Roles--------------------------------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role{
+    Admin, //every operation, grants and revokes roles
+    ProjectReviewer, //add, approve, complete and fail projects
+    CommunityManager, //add and remove community members
+    TreasuryOperator, //move contract funds, pick yield strategies
+}
+
+pub const ROLES: Map<Addr, Vec<Role>> = Map::new("roles");
+
+//------------Yield strategy-------------------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum YieldStrategy{
+    Anchor{ market: Addr, aust_token: Addr }, //anchor money market, aUST shares
+    Vault{ vault: Addr, share_token: Addr }, //generic vault minting cw20 shares
+    Hold, //keep raised funds in this contract, no yield
+}
+
+//-------------backer states, one per wallet of a project---------------
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct BackerState{
+    pub project_id: Uint128,
     pub backer_wallet: Addr,
-    pub ust_amount: Coin,
+    pub community: bool, //counted for community half, fixed on first backing
+    pub ust_amount: Coin, //uusd value of backed assets, net of fee
     pub aust_amount: Coin,
+    pub backed_assets: Vec<Asset>, //assets actually backed, net of fee
     pub otherchain: String,
     pub otherchain_wallet: String,
+    pub refunded: bool,
+    pub refund_shares: Uint128, //escrow shares claimable once project failed
+    pub refund_assets: Vec<Asset>, //held assets claimable once project failed
+    pub fee_bps: Uint128, //rate applied to the last backing
+    pub fee_amount: Uint128, //uusd value of fees charged, after discount
+    pub token_amount: Uint128, //tokens allocated over all contributions
+    pub delegate: Option<Addr>, //may vote on milestones for this backer
 }
+
+pub struct BackerIndexes<'a> {
+    pub wallet: MultiIndex<'a, (Addr, Vec<u8>), BackerState>,
+}
+
+impl<'a> IndexList<BackerState> for BackerIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<BackerState>> + '_> {
+        let v: Vec<&dyn Index<BackerState>> = vec![&self.wallet];
+        Box::new(v.into_iter())
+    }
+}
+
+//------------backers keyed by (project_id, wallet), indexed by wallet--------
+pub fn backers<'a>() -> IndexedMap<'a, (U128Key, Addr), BackerState, BackerIndexes<'a>> {
+    let indexes = BackerIndexes {
+        wallet: MultiIndex::new(
+            |backer: &BackerState, pk: Vec<u8>| (backer.backer_wallet.clone(), pk),
+            "backers",
+            "backers__wallet",
+        ),
+    };
+    IndexedMap::new("backers", indexes)
+}
+
+pub fn load_backers(store: &dyn Storage, project_id: Uint128) -> StdResult<Vec<BackerState>>
+{
+    backers()
+        .prefix(project_id.u128().into())
+        .range(store, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, backer)| backer))
+        .collect()
+}
+//--------------single backing, kept as history of the wallet---------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Contribution{
+    pub asset: Asset, //net of fee
+    pub fee_amount: Uint128, //uusd value
+    pub fundraising_stage: Uint128,
+    pub token_amount: Uint128,
+    pub timestamp: Uint128, //seconds
+}
+
+//------------contributions keyed by (project_id, wallet), oldest first-------
+pub const CONTRIBUTIONS: Map<(U128Key, Addr), Vec<Contribution>> = Map::new("contributions");
+
 //--------------Vote---------------------------------------
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Vote{
     pub wallet: Addr,
-    pub voted: bool,
+    pub voted: Option<bool>, //None until wallet votes
+    pub weight: Uint128, //ust backed by wallet
+    pub delegate: Option<Addr>, //set when delegate cast the vote
 }
 
 //--------------Milestone---------------------------------------
@@ -40,8 +191,81 @@ pub struct Milestone{
     pub milestone_startdate: String,
     pub milestone_enddate: String,
     pub milestone_amount: Uint128,
-    pub milestone_status: Uint128, //0:voting, 1:releasing 2:released
+    pub milestone_status: Uint128, //0:voting, 1:releasing 2:released 3:rejected
     pub milestone_votes: Vec<Vote>,
+    pub milestone_evidence: Vec<String>, //links backing the milestone delivery
+    pub milestone_rejections: Uint128,
+    pub milestone_votestart: Uint128, //seconds, voting window of milestone
+    pub milestone_voteend: Uint128,
+}
+//--------------Milestone voting rules of a project--------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MilestoneVoting{
+    pub quorum: Uint128, //% of backed weight that must vote
+    pub threshold: Uint128, //% of cast weight that must vote yes
+    pub vote_period: Uint128, //seconds
+    pub max_rejections: Uint128, //project fails on one more rejection of a milestone
+}
+pub const DEFAULT_QUORUM: u128 = 50;
+pub const DEFAULT_THRESHOLD: u128 = 50;
+pub const DEFAULT_VOTE_PERIOD: u128 = 604800; //1 week
+pub const DEFAULT_MAX_REJECTIONS: u128 = 2;
+//--------------Project meta data, validated on add and update-------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SocialLink{
+    pub name: String,
+    pub url: String,
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProjectMetadata{
+    pub company: String,
+    pub title: String,
+    pub description: String,
+    pub ecosystem: String,
+    pub created_at: Uint128, //seconds, block time project was added
+    pub saft: String,
+    pub logo: String,
+    pub whitepaper: String,
+    pub website: String,
+    pub email: String,
+    pub socials: Vec<SocialLink>,
+}
+//--------------Meta data kept off chain, only its hash and title here------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MetadataRef{
+    pub title: String,
+    pub created_at: Uint128, //seconds, block time project was added
+    pub sha256: String, //hex hash of the metadata blob
+    pub uri: String, //where blob is found, e.g. ipfs://<cid>
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Metadata{
+    OnChain(ProjectMetadata),
+    OffChain(MetadataRef),
+}
+impl Metadata{
+    pub fn title(&self) -> &str {
+        match self {
+            Metadata::OnChain(metadata) => &metadata.title,
+            Metadata::OffChain(metadata) => &metadata.title,
+        }
+    }
+
+    pub fn created_at(&self) -> Uint128 {
+        match self {
+            Metadata::OnChain(metadata) => metadata.created_at,
+            Metadata::OffChain(metadata) => metadata.created_at,
+        }
+    }
+
+    //only known when meta data is kept on chain
+    pub fn ecosystem(&self) -> Option<&str> {
+        match self {
+            Metadata::OnChain(metadata) => Some(&metadata.ecosystem),
+            Metadata::OffChain(_) => None,
+        }
+    }
 }
 //------------Team Description-------------------------------
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -54,64 +278,140 @@ pub struct TeamMember{
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VestingParameter{
     pub stage_title: String,
-    pub stage_price: Uint128,
-    pub stage_amount: Uint128,
+    pub stage_price: Uint128, //uusd per whole token
+    pub stage_amount: Uint128, //whole tokens sold in stage
     pub stage_soon: Uint128,
     pub stage_after: Uint128,
     pub stage_period: Uint128   
 }
 
+//--------------round of fundraising, one per vesting stage----------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FundraisingRound{
+    pub start: Uint128, //seconds
+    pub end: Uint128, //seconds
+    pub cap: Uint128, //backed amount at par
+    pub raised: Uint128,
+}
+
+//------------first round from stage on not ended or filled, len of rounds if none left------
+pub fn active_round(rounds: &[FundraisingRound], stage: Uint128, now: Uint128) -> Uint128 {
+    let mut stage = stage.u128() as usize;
+    while stage < rounds.len() 
+        && (now > rounds[stage].end || rounds[stage].raised >= rounds[stage].cap)
+    {
+        stage += 1;
+    }
+    Uint128::new(stage as u128)
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub enum ProjectStatus{
     WefundVote,
     Fundraising,
     Releasing,
     Done,
-    Fail
+    Fail,
+    Rejected, //not listed by community vote
 }
+
+impl ProjectStatus{
+    //key of project status index
+    pub fn key(&self) -> Vec<u8> {
+        vec![self.clone() as u8]
+    }
+
+    //statuses a project may move to, Done/Fail/Rejected are final
+    pub fn transitions(&self) -> &'static [ProjectStatus] {
+        match self {
+            ProjectStatus::WefundVote => &[ProjectStatus::Fundraising, ProjectStatus::Rejected],
+            ProjectStatus::Fundraising => &[ProjectStatus::Releasing, ProjectStatus::Fail],
+            ProjectStatus::Releasing => &[ProjectStatus::Done, ProjectStatus::Fail],
+            ProjectStatus::Done | ProjectStatus::Fail | ProjectStatus::Rejected => &[],
+        }
+    }
+
+    pub fn can_transition(&self, to: &ProjectStatus) -> bool {
+        self.transitions().contains(to)
+    }
+}
+
+//------------status changes of a project, oldest first-----------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatusTransition{
+    pub from: ProjectStatus,
+    pub to: ProjectStatus,
+    pub timestamp: Uint128, //seconds
+}
+
+pub const PROJECT_HISTORY: Map<U128Key, Vec<StatusTransition>> = Map::new("project_history");
 //------------ project state--------------------------------
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ProjectState{
 //---------mata data----------------------------------------------------------
-    pub project_company: String,
-    pub project_title: String,
-    pub project_description: String,
-    pub project_ecosystem: String,
-    pub project_createddate: String,
-    pub project_saft: String,
-    pub project_logo: String,
-    pub project_whitepaper: String,
-    pub project_website: String,
-    pub project_email: String,
+    pub metadata: Metadata,
 //------------------------------------------------------------------------------
     pub project_id: Uint128,
     pub creator_wallet: Addr,
     pub project_collected: Uint128,
 
-    //0:wefund voting 1:fundrasing 2:releasing 3:done 4:fail
-    pub project_status: ProjectStatus, 
+    pub project_status: ProjectStatus, //changed by transition_status only
     pub fundraising_stage: Uint128, 
+    pub fundraising_deadline: Option<Uint128>, //seconds, fail if not collected by then
+    pub fundraising_rounds: Vec<FundraisingRound>, //empty: single round, stage not enforced
 
     pub backerbacked_amount: Uint128,
     pub communitybacked_amount: Uint128,
-//---------backer states for 50% of collected------------------------    
-    pub backer_states: Vec<BackerState>,
-
-//---------community backer states for 50% of collected---------------
-    pub communitybacker_states: Vec<BackerState>,
-
-//----------milestone states-----------------------------------------
-    pub milestone_states: Vec<Milestone>,
+    pub backer_count: Uint128, //wallets backing public half
+    pub communitybacker_count: Uint128, //wallets backing community half
+//---------per wallet limits of backed amount, at par------------------
+    pub wallet_min_amount: Option<Uint128>,
+    pub wallet_max_amount: Option<Uint128>,
+//---------assets accepted for backing, valued in uusd at config rate-----------
+    pub accepted_assets: Vec<AssetInfo>,
+    pub backed_assets: Vec<Asset>, //collected per asset
+    pub held_assets: Vec<Asset>, //non uusd assets still held by contract
+//---------backers and milestones are in backers() and MILESTONES-----
+    pub milestone_count: Uint128,
     pub project_milestonestep: Uint128, 
-//---------team members-----------------------------------------------
-    pub teammember_states: Vec<TeamMember>,
+    pub milestone_voting: MilestoneVoting,
 //---------vesting-----------------------------------------------
     pub vesting: Vec<VestingParameter>,
+    pub stage_allocated: Vec<Uint128>, //token base units allocated per vesting stage
 
     pub token_addr: Addr,
+//---------project level override of config.yield_strategy, snapshot of it at first backing------
+    pub yield_strategy: Option<YieldStrategy>,
+//---------project level override of fee_config rate, set on approval------
+    pub fee_bps: Option<Uint128>,
+//---------escrow: principal still parked and share units it holds------
+    pub escrow_principal: Uint128,
+    pub escrow_shares: Uint128,
 }
 pub const PROJECT_SEQ: Item<Uint128> = Item::new("prj_seq");
-pub const PROJECTSTATES: Map<U128Key, ProjectState> = Map::new("prj");
+
+pub struct ProjectIndexes<'a> {
+    pub status: MultiIndex<'a, (Vec<u8>, Vec<u8>), ProjectState>,
+}
+
+impl<'a> IndexList<ProjectState> for ProjectIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ProjectState>> + '_> {
+        let v: Vec<&dyn Index<ProjectState>> = vec![&self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+//------------projects keyed by id, indexed by status------------------------
+pub fn projects<'a>() -> IndexedMap<'a, U128Key, ProjectState, ProjectIndexes<'a>> {
+    let indexes = ProjectIndexes {
+        status: MultiIndex::new(
+            |project: &ProjectState, pk: Vec<u8>| (project.project_status.key(), pk),
+            "prj",
+            "prj__status",
+        ),
+    };
+    IndexedMap::new("prj", indexes)
+}
 
 pub fn save_projectstate(store: &mut dyn Storage, _prj: &mut ProjectState) 
     -> StdResult<()> 
@@ -121,9 +421,139 @@ pub fn save_projectstate(store: &mut dyn Storage, _prj: &mut ProjectState)
     let id = id.checked_add(Uint128::new(1))?;
     PROJECT_SEQ.save(store, &id)?;
 
-    _prj.project_id = id.clone();
-    PROJECTSTATES.save(store, id.u128().into(), &_prj)
+    _prj.project_id = id;
+    projects().save(store, id.u128().into(), _prj)
 }
 
+//------------milestones keyed by (project_id, step)----------------------
+pub const MILESTONES: Map<(U128Key, U128Key), Milestone> = Map::new("milestones");
+
+pub fn load_milestone(store: &dyn Storage, project_id: Uint128, step: Uint128) -> StdResult<Milestone>
+{
+    MILESTONES.load(store, (project_id.u128().into(), step.u128().into()))
+}
+
+pub fn save_milestone(store: &mut dyn Storage, project_id: Uint128, step: Uint128, milestone: &Milestone)
+    -> StdResult<()>
+{
+    MILESTONES.save(store, (project_id.u128().into(), step.u128().into()), milestone)
+}
+
+pub fn load_milestones(store: &dyn Storage, project_id: Uint128) -> StdResult<Vec<Milestone>>
+{
+    MILESTONES
+        .prefix(project_id.u128().into())
+        .range(store, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, milestone)| milestone))
+        .collect()
+}
+
+//------------team members of a project---------------------------------------
+pub const TEAMMEMBERS: Map<U128Key, Vec<TeamMember>> = Map::new("teammembers");
+
+//------------where listing vote weights come from----------------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum WeightSource{
+    Community, //one vote per community member
+    Staking{ contract: Addr }, //tokens staked in staking contract
+}
+
+//------------rules of community vote listing a project--------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingVoting{
+    pub quorum: Uint128, //percent of total weight that must vote
+    pub threshold: Uint128, //percent of cast weight voting yes
+    pub vote_period: Uint128, //seconds
+    pub weight_source: WeightSource,
+}
+
+impl Default for ListingVoting{
+    fn default() -> Self {
+        ListingVoting{
+            quorum: Uint128::new(DEFAULT_QUORUM),
+            threshold: Uint128::new(DEFAULT_THRESHOLD),
+            vote_period: Uint128::new(DEFAULT_VOTE_PERIOD),
+            weight_source: WeightSource::Community,
+        }
+    }
+}
+
+pub const LISTING_VOTING: Item<ListingVoting> = Item::new("listing_voting");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ListingVote{
+    pub project_id: Uint128,
+    pub votestart: Uint128,
+    pub voteend: Uint128,
+    pub snapshot_height: u64, //weights are read as of this block
+    pub community: Vec<Addr>, //members when vote opened, one vote each
+    pub votes: Vec<Vote>,
+    pub passed: Option<bool>, //None until tallied
+}
+
+pub const LISTING_VOTES: Map<U128Key, ListingVote> = Map::new("listing_votes");
+
+//------------review of a project submitted by its creator-------------------
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub enum ReviewStatus{
+    Submitted,
+    ChangesRequested,
+    Approved,
+    Rejected,
+}
+
+impl ReviewStatus{
+    //key of review status index
+    pub fn key(&self) -> Vec<u8> {
+        vec![self.clone() as u8]
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReviewComment{
+    pub author: Addr,
+    pub status: ReviewStatus, //status the comment moved review to
+    pub comment: String,
+    pub timestamp: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Review{
+    pub project_id: Uint128,
+    pub creator_wallet: Addr,
+    pub title: String, //kept after rejected project is removed
+    pub status: ReviewStatus,
+    pub deposit: Uint128, //uusd, back to creator unless slashed
+    pub slashed: bool,
+    pub comments: Vec<ReviewComment>,
+}
+
+pub struct ReviewIndexes<'a> {
+    pub status: MultiIndex<'a, (Vec<u8>, Vec<u8>), Review>,
+}
+
+impl<'a> IndexList<Review> for ReviewIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Review>> + '_> {
+        let v: Vec<&dyn Index<Review>> = vec![&self.status];
+        Box::new(v.into_iter())
+    }
+}
+
+//------------reviews keyed by project id, indexed by status------------------
+pub fn reviews<'a>() -> IndexedMap<'a, U128Key, Review, ReviewIndexes<'a>> {
+    let indexes = ReviewIndexes {
+        status: MultiIndex::new(
+            |review: &Review, pk: Vec<u8>| (review.status.key(), pk),
+            "reviews",
+            "reviews__status",
+        ),
+    };
+    IndexedMap::new("reviews", indexes)
+}
+
+//------------anti-spam deposit of a submission, uusd------------------------
+pub const SUBMIT_DEPOSIT: Item<Uint128> = Item::new("submit_deposit");
+pub const DEFAULT_SUBMIT_DEPOSIT: u128 = 100000000; //100 UST
+
 //------------community array------------------------------------------------
 pub const COMMUNITY: Item<Vec<Addr>> = Item::new("community");
\ No newline at end of file
@@ -1,28 +1,63 @@
 use super::*;
-use cosmwasm_std::{from_binary, Addr, CosmosMsg, WasmMsg,
-    BankQuery, BalanceResponse, AllBalanceResponse, Coin, Uint128};
-use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR, mock_dependencies};
+use cosmwasm_std::{from_binary, to_binary, Addr, Binary, CosmosMsg, WasmMsg, BankMsg, Decimal,
+    BankQuery, BalanceResponse, AllBalanceResponse, Coin, StdError, Uint128};
+use cosmwasm_std::testing::{mock_env, mock_info, MOCK_CONTRACT_ADDR};
 
-use crate::contract::{execute, instantiate};
+use crate::contract::{execute, instantiate, migrate};
+use crate::error::ContractError;
+use crate::legacy::{LegacyConfig, LegacyProjectState, LegacyBackerState, LegacyMilestone, LegacyVote,
+    LEGACY_CONFIG, LEGACY_PROJECTSTATES};
 use crate::query::{query};
-use crate::state::{Milestone, Config, ProjectState};
-use crate::msg::{QueryMsg, ExecuteMsg, InstantiateMsg};
+use crate::state::{Milestone, Config, YieldStrategy, VestingParameter, FundraisingRound, ProjectState, ProjectStatus, MilestoneVoting, Role, BackerState,
+    Review, ReviewStatus, ListingVote, ListingVoting, WeightSource, DEFAULT_VOTE_PERIOD, StatusTransition,
+    FeeConfig, FeeTier, FeeSplit, projects};
+use crate::fee::calc_fee;
+use crate::yield_adapter::{YieldAdapter, AnchorAdapter, VaultAdapter, HoldAdapter};
+use crate::msg::{QueryMsg, ExecuteMsg, InstantiateMsg, RefundableResponse, MilestoneVotesResponse,
+    Cw20HookMsg, ProjectMetadataMsg, MetadataMsg, MetadataHashResponse, VerifyMetadataResponse,
+    ProjectResponse, EscrowResponse, MigrateMsg, ContributionsResponse, ProjectSummary, BackerKind, BackersResponse,
+    PortfolioResponse, ReviewDecision, FundraisingRoundResponse};
+use crate::asset::{Asset, AssetInfo, AssetRate};
 
-// use crate::mock_querier::mock_dependencies;
-use cw20::Cw20ExecuteMsg;
+use crate::mock_querier::mock_dependencies;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use Vesting::msg::ExecuteMsg as VestingMsg;
+use cosmwasm_bignumber::Decimal256;
 // use terraswap::asset::{Asset, AssetInfo};
 // use terraswap::pair::ExecuteMsg as TerraswapExecuteMsg;
 
+fn metadata() -> MetadataMsg {
+    MetadataMsg::OnChain(onchain_metadata())
+}
+
+fn onchain_metadata() -> ProjectMetadataMsg {
+    ProjectMetadataMsg{
+        company: "company".to_string(),
+        title: String::from("demo1"),
+        description: String::from("demo1"),
+        ecosystem: "terra".to_string(),
+        saft: String::from("https://demo1/saft"),
+        logo: String::from("https://demo1/icon1"),
+        whitepaper: String::from("https://demo1/whitepaper"),
+        website: String::from("https://demo1"),
+        email: String::from("deme1@gmail.com"),
+        socials: Vec::new(),
+    }
+}
+
 #[test]
 fn workflow(){
     let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_balances(&[(&"aust".to_string(), 
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::new(1000000000))])]);
+    deps.querier.with_token_decimals(&"token1".to_string(), 6);
     
     let msg = InstantiateMsg{
         admin: Some(String::from("admin")),
         wefund: Some(String::from("Wefund")),
-        anchor_market: Some( "market".to_string()),
-        aust_token: Some("aust".to_string()),
-        vesting_contract: Some("vesting".to_string())
+        yield_strategy: None,
+        vesting_contract: Some("vesting".to_string()),
+        timelock: None
     };
 //instantiate
     let info = mock_info("admin", &[]);
@@ -54,7 +89,11 @@ fn workflow(){
         milestone_enddate: String::from("enddate"),
         milestone_amount: Uint128::new(100),
         milestone_status: Uint128::new(0),
-        milestone_votes: Vec::new()
+        milestone_votes: Vec::new(),
+        milestone_evidence: Vec::new(),
+        milestone_rejections: Uint128::zero(),
+        milestone_votestart: Uint128::zero(),
+        milestone_voteend: Uint128::zero()
     };
     let milestone2 = Milestone{
         milestone_step: Uint128::new(1),
@@ -64,29 +103,27 @@ fn workflow(){
         milestone_enddate: String::from("enddate"),
         milestone_amount: Uint128::new(200),
         milestone_status: Uint128::new(0),
-        milestone_votes: Vec::new()
+        milestone_votes: Vec::new(),
+        milestone_evidence: Vec::new(),
+        milestone_rejections: Uint128::zero(),
+        milestone_votestart: Uint128::zero(),
+        milestone_voteend: Uint128::zero()
     };
     let milestone_states = vec![milestone1, milestone2];
     let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
         creator_wallet: String::from("terra1emwyg68n0wtglz8ex2n2728fnfzca9xkdc4aka"),
-        project_description: String::from("demo1"),
         project_collected: Uint128::new(300),
-        project_email: String::from("deme1@gmail.com"),
-        project_title: String::from("demo1"),
-        project_website: String::from("https://demo1"),
-        project_createddate: String::from("20211223"),
-        project_logo: String::from("icon1"),
-        project_whitepaper: String::from("whitepaper"),
         project_milestones: milestone_states,
-        project_company: "company".to_string(),
-        project_ecosystem: "terra".to_string(),
-        project_saft: "saft".to_string(),
         project_teammembers: Vec::new(),
         vesting: Vec::new(),
         token_addr: "token1".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
     };
     let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-    // assert_eq!(res.messages.len(), 0);
+    assert_eq!(res.messages.len(), 0); //vesting waits for listing
     println!("{:?}", res);
 
 //add project        
@@ -116,8 +153,20 @@ fn workflow(){
         let info = mock_info("admin", &[]);
         let msg = ExecuteMsg::WefundApprove{
             project_id: Uint128::new(1),
+            fee_bps: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute{
+            contract_addr: "vesting".to_string(),
+            msg: to_binary(&VestingMsg::AddProject{
+                project_id: Uint128::new(1),
+                admin: MOCK_CONTRACT_ADDR.to_string(),
+                token_addr: "token1".to_string(),
+                vesting_params: Vec::new(),
+                start_time: Uint128::zero(),
+            }).unwrap(),
+            funds: Vec::new(),
+        }));
         println!("WeFund Approve: {:?}", res);
 
         // let info = mock_info("admin", &[]);
@@ -145,7 +194,6 @@ fn workflow(){
             otherchain: "ethereum".to_string(),
             otherchain_wallet: "ether_wallet".to_string(),
             fundraising_stage: Uint128::zero(),
-            token_amount: Uint128::new(10)
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         println!("back2project:{:?}", res);
@@ -157,11 +205,14 @@ fn workflow(){
             otherchain: "ethereum".to_string(),
             otherchain_wallet: "ether_wallet".to_string(),
             fundraising_stage: Uint128::zero(),
-            token_amount: Uint128::new(10)
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         println!("back2project:{:?}", res);
 
+        let info = mock_info("admin", &[]);
+        let msg = ExecuteMsg::SetFundraisingStage{ project_id: Uint128::new(1), stage: Uint128::from(1u128) };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
         let info = mock_info("community1", &[Coin::new(210000000, "uusd")]);
         let msg = ExecuteMsg::Back2Project{
             project_id: Uint128::new(1),
@@ -169,7 +220,6 @@ fn workflow(){
             otherchain: "ethereum".to_string(),
             otherchain_wallet: "ether_wallet".to_string(),
             fundraising_stage: Uint128::from(1u128),
-            token_amount: Uint128::new(10)
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         println!("back2project:{:?}", res);
@@ -183,38 +233,56 @@ fn workflow(){
         let info = mock_info("backer1", &[]);
         let msg = ExecuteMsg::SetMilestoneVote{
             project_id: Uint128::new(1),
-            wallet: String::from("backer1"),
             voted: true,
+            on_behalf_of: None,
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         println!("set milestone vote:{:?}", res);
-
+// //delegate vote of backer2
         let info = mock_info("backer2", &[]);
-        let msg = ExecuteMsg::SetMilestoneVote{
+        let msg = ExecuteMsg::DelegateVote{
             project_id: Uint128::new(1),
-            wallet: String::from("backer2"),
-            voted: true,
+            delegate: Some(String::from("delegate1")),
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-        println!("set milestone vote:{:?}", res);
+        println!("delegate vote:{:?}", res);
 
-        let info = mock_info("backer1", &[]);
+        let info = mock_info("anyone", &[]);
         let msg = ExecuteMsg::SetMilestoneVote{
             project_id: Uint128::new(1),
-            wallet: String::from("backer1"),
-            voted: true,
+            voted: false,
+            on_behalf_of: Some(String::from("backer1")),
         };
-        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
-        println!("set milestone vote:{:?}", res);
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg);
+        assert!(res.is_err()); //not delegate of backer1
 
-        let info = mock_info("backer2", &[]);
+        let info = mock_info("delegate1", &[]);
         let msg = ExecuteMsg::SetMilestoneVote{
             project_id: Uint128::new(1),
-            wallet: String::from("backer2"),
             voted: true,
+            on_behalf_of: Some(String::from("backer2")),
         };
         let res = execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
         println!("set milestone vote:{:?}", res);
+
+        let msg = QueryMsg::GetMilestoneVotes{ project_id: Uint128::new(1), step: None };
+        let res:MilestoneVotesResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+        let vote = res.votes.iter().find(|vote| vote.wallet == Addr::unchecked("backer2")).unwrap();
+        assert_eq!(vote.voted, Some(true));
+        assert_eq!(vote.delegate, Some(Addr::unchecked("delegate1")));
+// //tally milestone vote
+        let msg = ExecuteMsg::TallyMilestone{ project_id: Uint128::new(1) };
+        let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+        assert!(res.is_err()); //voting window still open
+
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(604801);
+        let res = execute(deps.as_mut(), env, info.clone(), msg).unwrap();
+        assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send{
+            to_address: "terra1emwyg68n0wtglz8ex2n2728fnfzca9xkdc4aka".to_string(),
+            amount: vec![Coin::new(100000000, "uusd")]
+        }));
+        println!("tally milestone vote:{:?}", res);
         
 // //-Get Project-----------------
 //     let msg = QueryMsg::GetAllProject{};
@@ -258,3 +326,1857 @@ fn workflow(){
     println!("allproject {:?}", res );
 }
 
+
+#[test]
+fn expire_and_refund(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//add project with deadline
+    let deadline = mock_env().block.time.seconds() + 100;
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(300),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: Some(Uint128::from(deadline)),
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//back before deadline
+    let info = mock_info("backer1", &[Coin::new(105000000, "uusd")]);
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+//too early to expire
+    let msg = ExecuteMsg::ExpireProject{ project_id: Uint128::new(1) };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+    assert!(res.is_err());
+//expire after deadline and claim
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(101);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+
+    let msg = QueryMsg::GetRefundable{ project_id: Uint128::new(1), wallet: String::from("backer1") };
+    let refundable:RefundableResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(refundable.amount, Uint128::new(100000000));
+
+//nothing in escrow yet, claim fails and stays open
+    let mut project = projects().load(&deps.storage, 1u128.into()).unwrap();
+    let escrow_shares = project.escrow_shares;
+    project.escrow_shares = Uint128::zero();
+    projects().save(&mut deps.storage, 1u128.into(), &project).unwrap();
+    let msg = ExecuteMsg::ClaimRefund{ project_id: Uint128::new(1) };
+    let err = execute(deps.as_mut(), env.clone(), mock_info("backer1", &[]), msg.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::NothingToRefund{}));
+    project.escrow_shares = escrow_shares;
+    projects().save(&mut deps.storage, 1u128.into(), &project).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("backer1", &[]), msg.clone()).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: "backer1".to_string(),
+        amount: vec![Coin::new(100000000, "uusd")]
+    }));
+//nothing left for second claim
+    let res = execute(deps.as_mut(), env, mock_info("backer1", &[]), msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn repeated_contributions(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//same wallet backs twice, second time at next stage
+    for stage in 0..2u128 {
+        let msg = ExecuteMsg::SetFundraisingStage{ project_id: Uint128::new(1), stage: Uint128::new(stage) };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+        let info = mock_info("backer1", &[Coin::new(105000000, "uusd")]);
+        let msg = ExecuteMsg::Back2Project{
+            project_id: Uint128::new(1),
+            backer_wallet: String::from("backer1"),
+            otherchain: "ethereum".to_string(),
+            otherchain_wallet: "ether_wallet".to_string(),
+            fundraising_stage: Uint128::new(stage),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+//one position, two contributions
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.backer_states.len(), 1);
+//strategy of config fixed on project at first backing
+    assert_eq!(project.project.yield_strategy, Some(YieldStrategy::Hold));
+
+    let msg = QueryMsg::GetContributions{ project_id: Uint128::new(1), wallet: String::from("backer1") };
+    let res:ContributionsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.position.ust_amount.amount, Uint128::new(200000000));
+    assert_eq!(res.position.token_amount, Uint128::zero()); //no token, nothing allocated
+    assert_eq!(res.contributions.len(), 2);
+    assert_eq!(res.contributions[1].fundraising_stage, Uint128::new(1));
+    assert_eq!(res.contributions[1].asset.amount, Uint128::new(100000000));
+    assert_eq!(res.contributions[1].timestamp, Uint128::from(mock_env().block.time.seconds()));
+}
+
+#[test]
+fn transfer_accrued_fees(){
+//contract holds 200 UST escrow and 8 UST retained fees
+    let mut deps = mock_dependencies(&[Coin::new(208000000, "uusd")]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::TransferAllCoins{ wallet: String::from("treasury") };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone());
+    assert!(matches!(res.unwrap_err(), ContractError::NoAccruedFees{}));
+
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    for _ in 0..2 {
+        let info = mock_info("backer1", &[Coin::new(105000000, "uusd")]);
+        let msg = ExecuteMsg::Back2Project{
+            project_id: Uint128::new(1),
+            backer_wallet: String::from("backer1"),
+            otherchain: "ethereum".to_string(),
+            otherchain_wallet: "ether_wallet".to_string(),
+            fundraising_stage: Uint128::zero(),
+        };
+        execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    }
+    let res:Vec<Asset> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAccruedFees{}).unwrap()).unwrap();
+    assert_eq!(res, vec![Asset{ info: AssetInfo::uusd(), amount: Uint128::new(8000000) }]);
+//only the fees leave, escrow stays
+    let msg = ExecuteMsg::TransferAllCoins{ wallet: String::from("treasury") };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+    assert!(res.is_err());
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), msg.clone()).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: String::from("treasury"),
+        amount: vec![Coin::new(8000000, "uusd")],
+    }));
+    let res:Vec<Asset> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAccruedFees{}).unwrap()).unwrap();
+    assert_eq!(res, vec![]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert!(matches!(res.unwrap_err(), ContractError::NoAccruedFees{}));
+}
+
+#[test]
+fn submit_and_review(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let submit = ExecuteMsg::SubmitProject{
+        project_metadata: metadata(),
+        project_collected: Uint128::new(300),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+//deposit required
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[Coin::new(1000000, "uusd")]), submit.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[Coin::new(100000000, "uusd")]), submit.clone()).unwrap();
+
+    let msg = QueryMsg::ListPendingReviews{ start_after: None, limit: None };
+    let res:Vec<Review> = from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].creator_wallet, Addr::unchecked("creator"));
+//approved by review only
+    let approve = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    assert!(execute(deps.as_mut(), mock_env(), info.clone(), approve).is_err());
+//changes requested, creator resubmits
+    let review = |decision: ReviewDecision| ExecuteMsg::ReviewProject{
+        project_id: Uint128::new(1),
+        decision,
+        comment: String::from("comment"),
+        fee_bps: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), review(ReviewDecision::Approve));
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), info.clone(), review(ReviewDecision::RequestChanges)).unwrap();
+    let res:Vec<Review> = from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert!(res.is_empty());
+
+    let resubmit = ExecuteMsg::ResubmitProject{ project_id: Uint128::new(1), comment: String::from("done") };
+    assert!(execute(deps.as_mut(), mock_env(), mock_info("other", &[]), resubmit.clone()).is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), resubmit).unwrap();
+//approval returns deposit, sets project rate and opens listing vote
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::ReviewProject{
+        project_id: Uint128::new(1),
+        decision: ReviewDecision::Approve,
+        comment: String::from("comment"),
+        fee_bps: Some(Uint128::new(300)),
+    }).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: "creator".to_string(),
+        amount: vec![Coin::new(100000000, "uusd")]
+    }));
+    let query_project = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), query_project).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::WefundVote);
+    assert_eq!(project.project.fee_bps, Some(Uint128::new(300)));
+    let msg = QueryMsg::GetListingVote{ project_id: Uint128::new(1) };
+    let vote:Option<ListingVote> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(vote.unwrap().passed, None);
+//spam rejected, deposit slashed
+    execute(deps.as_mut(), mock_env(), mock_info("spammer", &[Coin::new(100000000, "uusd")]), submit).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), ExecuteMsg::ReviewProject{
+        project_id: Uint128::new(2),
+        decision: ReviewDecision::Reject{ slash: true },
+        comment: String::from("spam"),
+        fee_bps: None,
+    }).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: "Wefund".to_string(),
+        amount: vec![Coin::new(100000000, "uusd")]
+    }));
+    assert!(query(deps.as_ref(), mock_env(), QueryMsg::GetProject{ project_id: Uint128::new(2) }).is_err());
+
+    let msg = QueryMsg::GetReview{ project_id: Uint128::new(2) };
+    let res:Option<Review> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    let res = res.unwrap();
+    assert_eq!(res.status, ReviewStatus::Rejected);
+    assert!(res.slashed);
+    assert_eq!(res.comments[0].comment, String::from("spam"));
+}
+
+#[test]
+fn hard_cap(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(300),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//min above max rejected, only reviewer sets limits
+    let msg = ExecuteMsg::SetWalletLimits{ project_id: Uint128::new(1), 
+        min_amount: Some(Uint128::new(100000000)), max_amount: Some(Uint128::new(10000000)) };
+    assert!(execute(deps.as_mut(), mock_env(), info.clone(), msg).is_err());
+    let msg = ExecuteMsg::SetWalletLimits{ project_id: Uint128::new(1), 
+        min_amount: Some(Uint128::new(20000000)), max_amount: Some(Uint128::new(100000000)) };
+    assert!(execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg.clone()).is_err());
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let back = |wallet: &str| ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from(wallet),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+//below wallet minimum
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(15000000, "uusd")]), back("backer1"));
+    assert!(res.is_err());
+//over wallet maximum, excess returned
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(210000000, "uusd")]), back("backer1")).unwrap();
+    assert_eq!(res.attributes[1].value, "100000000".to_string());
+    assert_eq!(res.attributes[2].value, "105000000".to_string());
+    assert_eq!(res.messages[1].msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: "backer1".to_string(),
+        amount: vec![Coin::new(105000000, "uusd")]
+    }));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(30000000, "uusd")]), back("backer1"));
+    assert!(res.is_err());
+//only what is left of half accepted
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer2", &[Coin::new(105000000, "uusd")]), back("backer2")).unwrap();
+    assert_eq!(res.attributes[1].value, "50000000".to_string());
+    assert_eq!(res.attributes[2].value, "50000000".to_string());
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.backerbacked_amount, Uint128::new(150000000));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer3", &[Coin::new(105000000, "uusd")]), back("backer3"));
+    assert!(res.is_err());
+}
+
+#[test]
+fn list_backers(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddCommunitymember{ wallet: String::from("community1") };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    for wallet in ["backer1", "backer2", "community1", "backer1"] {
+        let msg = ExecuteMsg::Back2Project{
+            project_id: Uint128::new(1),
+            backer_wallet: String::from(wallet),
+            otherchain: "ethereum".to_string(),
+            otherchain_wallet: "ether_wallet".to_string(),
+            fundraising_stage: Uint128::zero(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(wallet, &[Coin::new(105000000, "uusd")]), msg).unwrap();
+    }
+//community backers listed too, wallets counted once
+    let msg = QueryMsg::ListBackers{ project_id: Uint128::new(1), kind: BackerKind::All, start_after: None, limit: None };
+    let res:BackersResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.backers.len(), 3);
+    assert_eq!(res.backer_count, Uint128::new(2));
+    assert_eq!(res.communitybacker_count, Uint128::new(1));
+    assert_eq!(res.backerbacked_amount, Uint128::new(300000000));
+
+    let msg = QueryMsg::ListBackers{ project_id: Uint128::new(1), kind: BackerKind::Community, start_after: None, limit: None };
+    let res:BackersResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.backers.len(), 1);
+    assert_eq!(res.backers[0].backer_wallet, Addr::unchecked("community1"));
+//pages by wallet
+    let msg = QueryMsg::ListBackers{ project_id: Uint128::new(1), kind: BackerKind::Public, 
+        start_after: Some(String::from("backer1")), limit: Some(1) };
+    let res:BackersResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.backers.len(), 1);
+    assert_eq!(res.backers[0].backer_wallet, Addr::unchecked("backer2"));
+
+    let msg = QueryMsg::GetBackerPosition{ project_id: Uint128::new(1), wallet: String::from("backer1") };
+    let res:Option<BackerState> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.unwrap().ust_amount.amount, Uint128::new(200000000));
+    let msg = QueryMsg::GetBackerPosition{ project_id: Uint128::new(1), wallet: String::from("nobody") };
+    let res:Option<BackerState> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res, None);
+}
+
+#[test]
+fn list_projects(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    for creator in ["creator1", "creator2", "creator1"] {
+        let msg = ExecuteMsg::AddProject{
+            project_metadata: metadata(),
+            creator_wallet: String::from(creator),
+            project_collected: Uint128::new(300),
+            project_milestones: Vec::new(),
+            project_teammembers: Vec::new(),
+            vesting: Vec::new(),
+            token_addr: "".to_string(),
+            fundraising_deadline: None,
+            milestone_voting: None,
+            accepted_assets: None,
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(2), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//pages in id order
+    let msg = QueryMsg::ListProjects{ start_after: Some(Uint128::new(1)), limit: Some(1), 
+        status: None, ecosystem: None, creator: None };
+    let res:Vec<ProjectSummary> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].project_id, Uint128::new(2));
+    assert_eq!(res[0].title, "demo1".to_string());
+//by status index
+    let msg = QueryMsg::ListProjects{ start_after: None, limit: None, 
+        status: Some(ProjectStatus::Fundraising), ecosystem: None, creator: None };
+    let res:Vec<ProjectSummary> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].project_id, Uint128::new(2));
+
+    let msg = QueryMsg::ListProjects{ start_after: Some(Uint128::new(1)), limit: None, 
+        status: Some(ProjectStatus::WefundVote), ecosystem: None, creator: None };
+    let res:Vec<ProjectSummary> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 1);
+    assert_eq!(res[0].project_id, Uint128::new(3));
+//by creator and ecosystem
+    let msg = QueryMsg::ListProjects{ start_after: None, limit: None, 
+        status: None, ecosystem: Some("terra".to_string()), creator: Some("creator1".to_string()) };
+    let res:Vec<ProjectSummary> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.len(), 2);
+
+    let msg = QueryMsg::ListProjects{ start_after: None, limit: None, 
+        status: None, ecosystem: Some("ethereum".to_string()), creator: None };
+    let res:Vec<ProjectSummary> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(res.is_empty());
+}
+
+#[test]
+fn milestone_rejection(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddCommunitymember{ wallet: String::from("community1") };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//add project failing on second rejection
+    let milestone = Milestone{
+        milestone_step: Uint128::new(0),
+        milestone_name: String::from("milestone1"),
+        milestone_description: String::from("mileston1"),
+        milestone_startdate: String::from("startdate"),
+        milestone_enddate: String::from("enddate"),
+        milestone_amount: Uint128::new(100),
+        milestone_status: Uint128::new(0),
+        milestone_votes: Vec::new(),
+        milestone_evidence: Vec::new(),
+        milestone_rejections: Uint128::zero(),
+        milestone_votestart: Uint128::zero(),
+        milestone_voteend: Uint128::zero()
+    };
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(200),
+        project_milestones: vec![milestone],
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: Some(MilestoneVoting{
+            quorum: Uint128::new(50),
+            threshold: Uint128::new(50),
+            vote_period: Uint128::new(100),
+            max_rejections: Uint128::new(1),
+        }),
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//malformed backer wallet is an error, not a panic
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from("b"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("b", &[Coin::new(105000000, "uusd")]), msg).unwrap_err();
+    assert!(matches!(err, ContractError::Std(_)));
+//back full amount, switch to releasing
+    for wallet in ["backer1", "community1"] {
+        let msg = ExecuteMsg::Back2Project{
+            project_id: Uint128::new(1),
+            backer_wallet: String::from(wallet),
+            otherchain: "ethereum".to_string(),
+            otherchain_wallet: "ether_wallet".to_string(),
+            fundraising_stage: Uint128::zero(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(wallet, &[Coin::new(105000000, "uusd")]), msg).unwrap();
+    }
+//portfolio shows the open vote
+    let portfolio = QueryMsg::GetWalletPortfolio{ wallet: String::from("backer1") };
+    let res:PortfolioResponse = from_binary(&query(deps.as_ref(), mock_env(), portfolio.clone()).unwrap()).unwrap();
+    assert_eq!(res.positions.len(), 1);
+    assert_eq!(res.positions[0].principal, Uint128::new(100000000));
+    assert_eq!(res.positions[0].token_amount, Uint128::zero());
+    assert_eq!(res.positions[0].vesting, None);
+    assert!(res.positions[0].can_vote);
+    assert_eq!(res.positions[0].pending_vote, Some(Uint128::zero()));
+//reject first time
+    let vote = ExecuteMsg::SetMilestoneVote{
+        project_id: Uint128::new(1),
+        voted: false,
+        on_behalf_of: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("backer1", &[]), vote.clone()).unwrap();
+    let res:PortfolioResponse = from_binary(&query(deps.as_ref(), mock_env(), portfolio).unwrap()).unwrap();
+    assert_eq!(res.positions[0].pending_vote, None);
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(101);
+    let tally = ExecuteMsg::TallyMilestone{ project_id: Uint128::new(1) };
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), tally.clone()).unwrap();
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(project.milestone_states[0].milestone_status, Uint128::new(3));
+//only creator resubmits
+    let resubmit = ExecuteMsg::ResubmitMilestone{
+        project_id: Uint128::new(1),
+        description: String::from("mileston1 done"),
+        evidence: vec![String::from("https://demo1/release")],
+    };
+    let res = execute(deps.as_mut(), env.clone(), mock_info("backer1", &[]), resubmit.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("creator", &[]), resubmit).unwrap();
+//reject again, project fails
+    execute(deps.as_mut(), env.clone(), mock_info("backer1", &[]), vote).unwrap();
+    env.block.time = env.block.time.plus_seconds(101);
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), tally).unwrap();
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::Fail);
+
+    let msg = QueryMsg::GetRefundable{ project_id: Uint128::new(1), wallet: String::from("community1") };
+    let refundable:RefundableResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(refundable.amount, Uint128::new(100000000));
+}
+
+#[test]
+fn release_all_milestones(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: Some(YieldStrategy::Vault{
+            vault: Addr::unchecked("vault"),
+            share_token: Addr::unchecked("share"),
+        }),
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let milestones: Vec<Milestone> = [100u128, 200].iter().enumerate().map(|(step, amount)| Milestone{
+        milestone_step: Uint128::new(step as u128),
+        milestone_name: format!("milestone{}", step + 1),
+        milestone_description: String::from("milestone"),
+        milestone_startdate: String::from("startdate"),
+        milestone_enddate: String::from("enddate"),
+        milestone_amount: Uint128::new(*amount),
+        milestone_status: Uint128::zero(),
+        milestone_votes: Vec::new(),
+        milestone_evidence: Vec::new(),
+        milestone_rejections: Uint128::zero(),
+        milestone_votestart: Uint128::zero(),
+        milestone_voteend: Uint128::zero()
+    }).collect();
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(300),
+        project_milestones: milestones,
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddCommunitymember{ wallet: String::from("community1") };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//each half raises 150 UST net of fee
+    for backer in ["backer1", "community1"].iter() {
+        let msg = ExecuteMsg::Back2Project{
+            project_id: Uint128::new(1),
+            backer_wallet: backer.to_string(),
+            otherchain: "ethereum".to_string(),
+            otherchain_wallet: "ether_wallet".to_string(),
+            fundraising_stage: Uint128::zero(),
+        };
+        execute(deps.as_mut(), mock_env(), mock_info(backer, &[Coin::new(157500000, "uusd")]), msg).unwrap();
+    }
+//escrow earns 10% while milestones run
+    deps.querier.with_vault_rate(Decimal::percent(110));
+    let msg = QueryMsg::GetProjectEscrow{ project_id: Uint128::new(1) };
+    let res:EscrowResponse = from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.principal, Uint128::new(300000000));
+    assert_eq!(res.shares, Uint128::new(300000000));
+    assert_eq!(res.value, Uint128::new(330000000));
+    assert_eq!(res.accrued_yield, Uint128::new(30000000));
+//first milestone releases its amount
+    let mut env = mock_env();
+    for backer in ["backer1", "community1"].iter() {
+        let vote = ExecuteMsg::SetMilestoneVote{ project_id: Uint128::new(1), voted: true, on_behalf_of: None };
+        execute(deps.as_mut(), env.clone(), mock_info(backer, &[]), vote).unwrap();
+    }
+    env.block.time = env.block.time.plus_seconds(604801);
+    let tally = ExecuteMsg::TallyMilestone{ project_id: Uint128::new(1) };
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), tally.clone()).unwrap();
+    assert_eq!(res.messages.last().unwrap().msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: String::from("creator"),
+        amount: vec![Coin::new(100000000, "uusd")],
+    }));
+    let res:EscrowResponse = from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.principal, Uint128::new(200000000));
+    assert_eq!(res.shares, Uint128::new(209090909));
+//last milestone empties escrow, yield included
+    for backer in ["backer1", "community1"].iter() {
+        let vote = ExecuteMsg::SetMilestoneVote{ project_id: Uint128::new(1), voted: true, on_behalf_of: None };
+        execute(deps.as_mut(), env.clone(), mock_info(backer, &[]), vote).unwrap();
+    }
+    env.block.time = env.block.time.plus_seconds(604801);
+    let res = execute(deps.as_mut(), env.clone(), info.clone(), tally).unwrap();
+    assert_eq!(res.messages.last().unwrap().msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: String::from("creator"),
+        amount: vec![Coin::new(229999999, "uusd")],
+    }));
+    let res:EscrowResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.principal, Uint128::zero());
+    assert_eq!(res.shares, Uint128::zero());
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::Done);
+//nothing left to complete
+    let msg = ExecuteMsg::CompleteProject{ project_id: Uint128::new(1) };
+    assert!(execute(deps.as_mut(), env, info, msg).is_err());
+}
+
+#[test]
+fn roles(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+//no role, no community management
+    let add_member = ExecuteMsg::AddCommunitymember{ wallet: String::from("community1") };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), add_member.clone());
+    assert!(res.is_err());
+//only admins grant roles
+    let grant = ExecuteMsg::GrantRole{ wallet: String::from("manager"), role: Role::CommunityManager };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), grant.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), grant).unwrap();
+
+    let msg = QueryMsg::GetRoles{ wallet: String::from("manager") };
+    let roles:Vec<Role> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(roles, vec![Role::CommunityManager]);
+
+    execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), add_member).unwrap();
+//role does not cover other operations
+    let msg = ExecuteMsg::SetFundraisingStage{ project_id: Uint128::new(1), stage: Uint128::new(1) };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), msg);
+    assert!(res.is_err());
+//revoked role
+    let msg = ExecuteMsg::RevokeRole{ wallet: String::from("manager"), role: Role::CommunityManager };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let msg = ExecuteMsg::RemoveCommunitymember{ wallet: String::from("community1") };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("manager", &[]), msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn ownership_and_timelock(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: Some(Uint128::new(100))
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+//config change waits for timelock
+    let msg = ExecuteMsg::SetConfig{
+        wefund: Some(String::from("treasury")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+
+    let msg = ExecuteMsg::ExecuteConfig{};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg.clone());
+    assert!(res.is_err());
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+    let config:Config = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::GetConfig{}).unwrap()).unwrap();
+    assert_eq!(config.wefund, Addr::unchecked("treasury"));
+//ownership moves only once accepted
+    let msg = ExecuteMsg::ProposeOwner{ owner: String::from("newadmin") };
+    execute(deps.as_mut(), env.clone(), mock_info("admin", &[]), msg).unwrap();
+
+    let res = execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), ExecuteMsg::AcceptOwnership{});
+    assert!(res.is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("newadmin", &[]), ExecuteMsg::AcceptOwnership{}).unwrap();
+
+    let config:Config = from_binary(&query(deps.as_ref(), env.clone(), QueryMsg::GetConfig{}).unwrap()).unwrap();
+    assert_eq!(config.owner, Addr::unchecked("newadmin"));
+    let pending:Option<Addr> = from_binary(&query(deps.as_ref(), env, QueryMsg::GetPendingOwner{}).unwrap()).unwrap();
+    assert_eq!(pending, None);
+}
+
+#[test]
+fn cw20_backing(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//add project accepting uusd and a cw20 stable whitelisted at par
+    let deadline = mock_env().block.time.seconds() + 100;
+    let stable = AssetInfo::Cw20{ contract_addr: Addr::unchecked("stable") };
+    let msg = ExecuteMsg::SetAcceptedAssets{ assets: vec![
+        AssetRate{ info: stable.clone(), decimals: 6, rate: Decimal::one() },
+    ]};
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(300),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: Some(Uint128::from(deadline)),
+        milestone_voting: None,
+        accepted_assets: Some(vec![AssetInfo::uusd(), stable.clone()]),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//reject not accepted and multiple denoms
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    let info = mock_info("backer1", &[Coin::new(105000000, "uluna")]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg.clone());
+    assert!(res.is_err());
+    let info = mock_info("backer1", &[Coin::new(105000000, "uusd"), Coin::new(1, "uluna")]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    assert!(res.is_err());
+//back with cw20 through receive hook
+    let hook = Cw20HookMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg{
+        sender: String::from("backer1"),
+        amount: Uint128::new(105000000),
+        msg: to_binary(&hook).unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), mock_info("stable", &[]), msg.clone()).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute{
+        contract_addr: "stable".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer{
+            recipient: "Wefund".to_string(),
+            amount: Uint128::new(1000000),
+        }).unwrap(),
+        funds: Vec::new(),
+    }));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("other_token", &[]), msg);
+    assert!(res.is_err());
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.backerbacked_amount, Uint128::new(100000000));
+    assert_eq!(project.project.held_assets, vec![Asset{ info: stable.clone(), amount: Uint128::new(100000000) }]);
+    assert_eq!(project.project.escrow_shares, Uint128::zero());
+    assert_eq!(project.backer_states[0].fee_amount, Uint128::new(5000000));
+//retained part of cw20 fee is paid out by TransferAllCoins
+    let res:Vec<Asset> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAccruedFees{}).unwrap()).unwrap();
+    assert_eq!(res, vec![Asset{ info: stable.clone(), amount: Uint128::new(4000000) }]);
+    let msg = ExecuteMsg::TransferAllCoins{ wallet: String::from("treasury") };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    assert_eq!(res.messages.len(), 1);
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute{
+        contract_addr: "stable".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer{
+            recipient: "treasury".to_string(),
+            amount: Uint128::new(4000000),
+        }).unwrap(),
+        funds: Vec::new(),
+    }));
+    let res:Vec<Asset> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAccruedFees{}).unwrap()).unwrap();
+    assert_eq!(res, vec![]);
+//expire and refund in the backed token
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(101);
+    let msg = ExecuteMsg::ExpireProject{ project_id: Uint128::new(1) };
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), msg).unwrap();
+
+    let msg = QueryMsg::GetRefundable{ project_id: Uint128::new(1), wallet: String::from("backer1") };
+    let refundable:RefundableResponse = from_binary(&query(deps.as_ref(), env.clone(), msg).unwrap()).unwrap();
+    assert_eq!(refundable.shares, Uint128::zero());
+    assert_eq!(refundable.assets, vec![Asset{ info: stable.clone(), amount: Uint128::new(100000000) }]);
+
+    let msg = ExecuteMsg::ClaimRefund{ project_id: Uint128::new(1) };
+    let res = execute(deps.as_mut(), env, mock_info("backer1", &[]), msg).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute{
+        contract_addr: "stable".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer{
+            recipient: "backer1".to_string(),
+            amount: Uint128::new(100000000),
+        }).unwrap(),
+        funds: Vec::new(),
+    }));
+}
+
+#[test]
+fn asset_rates(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let gov = AssetInfo::Cw20{ contract_addr: Addr::unchecked("gov") };
+    let add_project = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: Some(vec![gov.clone()]),
+    };
+//projects only accept whitelisted assets
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), add_project.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::NotAcceptedAsset{ .. }));
+//whitelist is owner only and needs a rate
+    let set_assets = |rate: Decimal| ExecuteMsg::SetAcceptedAssets{ assets: vec![
+        AssetRate{ info: gov.clone(), decimals: 18, rate },
+    ]};
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), set_assets(Decimal::percent(50)));
+    assert!(matches!(res.unwrap_err(), ContractError::Unauthorized{}));
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), set_assets(Decimal::zero()));
+    assert!(matches!(res.unwrap_err(), ContractError::InvalidAssetRate{ .. }));
+    execute(deps.as_mut(), mock_env(), info.clone(), set_assets(Decimal::percent(50))).unwrap();
+
+    execute(deps.as_mut(), mock_env(), info.clone(), add_project).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+//210 gov at 0.5 UST is worth 105 UST, fee and weight count that
+    let hook = Cw20HookMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    let msg = ExecuteMsg::Receive(Cw20ReceiveMsg{
+        sender: String::from("backer1"),
+        amount: Uint128::new(210_000000000000000000),
+        msg: to_binary(&hook).unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), mock_info("gov", &[]), msg).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Wasm(WasmMsg::Execute{
+        contract_addr: "gov".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Transfer{
+            recipient: "Wefund".to_string(),
+            amount: Uint128::new(2_000000000000000000),
+        }).unwrap(),
+        funds: Vec::new(),
+    }));
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.backerbacked_amount, Uint128::new(100000000));
+    assert_eq!(project.project.held_assets, vec![Asset{ info: gov.clone(), amount: Uint128::new(200_000000000000000000) }]);
+    assert_eq!(project.backer_states[0].ust_amount.amount, Uint128::new(100000000));
+    assert_eq!(project.backer_states[0].fee_amount, Uint128::new(5000000));
+    let res:Vec<Asset> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAccruedFees{}).unwrap()).unwrap();
+    assert_eq!(res, vec![Asset{ info: gov, amount: Uint128::new(8_000000000000000000) }]);
+}
+
+#[test]
+fn project_fee_override(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//rate over 100% rejected
+    let approve = |bps: u128| ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: Some(Uint128::new(bps)) };
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), approve(10001)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidFeeConfig{}));
+    execute(deps.as_mut(), mock_env(), info, approve(1000)).unwrap();
+//10% on top: 220 UST backs 200, fee 20 of which 4 retained
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(220000000, "uusd")]), msg).unwrap();
+    assert_eq!(res.attributes[1].value, "200000000".to_string());
+    assert!(res.messages.iter().any(|msg| msg.msg == CosmosMsg::Bank(BankMsg::Send{
+        to_address: "Wefund".to_string(),
+        amount: vec![Coin::new(16000000, "uusd")]
+    })));
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.fee_bps, Some(Uint128::new(1000)));
+    assert_eq!(project.backer_states[0].fee_bps, Uint128::new(1000));
+    assert_eq!(project.backer_states[0].fee_amount, Uint128::new(20000000));
+    assert_eq!(project.backer_states[0].ust_amount.amount, Uint128::new(200000000));
+}
+
+#[test]
+fn fee_schedule(){
+    let wefund = Addr::unchecked("Wefund");
+    let fee_config = FeeConfig::default();
+//flat fee below 105 UST, 4 UST retained
+    let fee = calc_fee(&fee_config, &wefund, Uint128::new(50000000), None, false).unwrap();
+    assert_eq!(fee.backed, Uint128::new(45000000));
+    assert_eq!(fee.retained, Uint128::new(4000000));
+    assert_eq!(fee.payouts, vec![(wefund.clone(), Uint128::new(1000000))]);
+//5/105 above it
+    let fee = calc_fee(&fee_config, &wefund, Uint128::new(210000000), None, false).unwrap();
+    assert_eq!(fee.backed, Uint128::new(200000000));
+    assert_eq!(fee.payouts, vec![(wefund.clone(), Uint128::new(6000000))]);
+//below minimum contribution
+    assert!(calc_fee(&fee_config, &wefund, Uint128::new(5999999), None, false).is_err());
+//tier and split
+    let fee_config = FeeConfig{
+        tiers: vec![FeeTier{ min_amount: Uint128::new(1000000000), fee_bps: Uint128::new(250) }],
+        splits: vec![FeeSplit{ recipient: Addr::unchecked("partner"), share_bps: Uint128::new(5000) }],
+        ..FeeConfig::default()
+    };
+    let fee = calc_fee(&fee_config, &wefund, Uint128::new(1025000000), None, false).unwrap();
+    assert_eq!(fee.backed, Uint128::new(1000000000));
+    assert_eq!(fee.payouts, vec![
+        (Addr::unchecked("partner"), Uint128::new(10500000)),
+        (wefund.clone(), Uint128::new(10500000)),
+    ]);
+//project rate overrides tiers
+    let fee = calc_fee(&fee_config, &wefund, Uint128::new(1100000000), Some(Uint128::new(1000)), false).unwrap();
+    assert_eq!(fee.bps, Uint128::new(1000));
+    assert_eq!(fee.backed, Uint128::new(1000000000));
+//community discount and waiver
+    let discount = FeeConfig{ community_discount_bps: Uint128::new(5000), ..FeeConfig::default() };
+    let fee = calc_fee(&discount, &wefund, Uint128::new(50000000), None, true).unwrap();
+    assert_eq!(fee.fee, Uint128::new(2500000));
+    assert_eq!(fee.payouts, vec![]);
+    let waiver = FeeConfig{ community_discount_bps: Uint128::new(10000), ..FeeConfig::default() };
+    let fee = calc_fee(&waiver, &wefund, Uint128::new(50000000), None, true).unwrap();
+    assert_eq!(fee.backed, Uint128::new(50000000));
+    let fee = calc_fee(&waiver, &wefund, Uint128::new(50000000), None, false).unwrap();
+    assert_eq!(fee.backed, Uint128::new(45000000));
+//set by admin only, invalid rejected
+    let mut deps = mock_dependencies(&[]);
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    instantiate(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let msg = ExecuteMsg::SetFeeConfig{ fee_config: fee_config.clone() };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), msg.clone());
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg).unwrap();
+    let res:FeeConfig = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetFeeConfig{}).unwrap()).unwrap();
+    assert_eq!(res, fee_config);
+
+    let msg = ExecuteMsg::SetFeeConfig{ fee_config: FeeConfig{ fee_bps: Uint128::new(10001), ..FeeConfig::default() } };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("admin", &[]), msg);
+    assert!(res.is_err());
+}
+
+#[test]
+fn project_metadata(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//invalid email and website rejected
+    let add = |project_metadata: MetadataMsg| ExecuteMsg::AddProject{
+        project_metadata,
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(300),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(),
+        add(MetadataMsg::OnChain(ProjectMetadataMsg{ email: "deme1.gmail.com".to_string(), ..onchain_metadata() })));
+    assert!(res.is_err());
+    let res = execute(deps.as_mut(), mock_env(), info.clone(),
+        add(MetadataMsg::OnChain(ProjectMetadataMsg{ website: "demo1".to_string(), ..onchain_metadata() })));
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), info.clone(), add(metadata())).unwrap();
+//only creator updates, created_at kept
+    let update = ExecuteMsg::UpdateProjectMetadata{
+        project_id: Uint128::new(1),
+        project_metadata: MetadataMsg::OnChain(ProjectMetadataMsg{ title: "demo2".to_string(), ..onchain_metadata() }),
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), update.clone());
+    assert!(res.is_err());
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let res = execute(deps.as_mut(), env, mock_info("creator", &[]), update).unwrap();
+    assert_eq!(res.events[0].attributes[1].value, "title".to_string());
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.metadata.title(), "demo2");
+    assert_eq!(project.project.metadata.created_at(), Uint128::from(mock_env().block.time.seconds()));
+}
+
+#[test]
+fn offchain_metadata(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+//only title and hash of the blob on chain
+    let blob = Binary::from(b"{\"description\":\"large description\"}".to_vec());
+    let sha256 = crate::metadata::sha256_hex(blob.as_slice());
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: MetadataMsg::OffChain{
+            title: String::from("demo1"),
+            sha256: sha256.to_uppercase(),
+            uri: String::from("ipfs://bafkreigh2akiscaildc"),
+        },
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(300),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = QueryMsg::GetProjectMetadataHash{ project_id: Uint128::new(1) };
+    let res:MetadataHashResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.sha256, sha256);
+    assert!(!res.on_chain);
+//blob checked against stored hash
+    let msg = QueryMsg::VerifyMetadata{ project_id: Uint128::new(1), blob };
+    let res:VerifyMetadataResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(res.valid);
+    let msg = QueryMsg::VerifyMetadata{ project_id: Uint128::new(1), blob: Binary::from(b"tampered".to_vec()) };
+    let res:VerifyMetadataResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(!res.valid);
+//switching to on chain meta data emits storage change
+    let msg = ExecuteMsg::UpdateProjectMetadata{ project_id: Uint128::new(1), project_metadata: metadata() };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("creator", &[]), msg).unwrap();
+    assert_eq!(res.events[0].attributes[1].value, "storage".to_string());
+}
+
+#[test]
+fn migrate_split_state(){
+    let mut deps = mock_dependencies(&[Coin::new(72000000, "uusd")]);
+    deps.querier.with_token_balances(&[(&"aust".to_string(), 
+        &[(&MOCK_CONTRACT_ADDR.to_string(), &Uint128::new(200000000))])]);
+//state left by the first release
+    cw2::set_contract_version(deps.as_mut().storage, "WEFUND", "17.2.3").unwrap();
+    LEGACY_CONFIG.save(deps.as_mut().storage, &LegacyConfig{
+        owner: Addr::unchecked("admin"),
+        wefund: Addr::unchecked("Wefund"),
+        anchor_market: Addr::unchecked("market"),
+        aust_token: Addr::unchecked("aust"),
+        vesting_contract: Addr::unchecked(""),
+    }).unwrap();
+    let backing = |wallet: &str, amount: u128| LegacyBackerState{
+        backer_wallet: Addr::unchecked(wallet),
+        ust_amount: Coin::new(amount, "uusd"),
+        aust_amount: Coin::new(0, "aust"),
+        otherchain: String::from("terra"),
+        otherchain_wallet: String::new(),
+    };
+    let milestone = |step: u128, amount: u128, status: u128, votes: Vec<LegacyVote>| LegacyMilestone{
+        milestone_step: Uint128::new(step),
+        milestone_name: format!("milestone{}", step + 1),
+        milestone_description: String::from("milestone"),
+        milestone_startdate: String::from("startdate"),
+        milestone_enddate: String::from("enddate"),
+        milestone_amount: Uint128::new(amount),
+        milestone_status: Uint128::new(status),
+        milestone_votes: votes,
+    };
+    let project = |id: u128, status: ProjectStatus, backer_states: Vec<LegacyBackerState>,
+        communitybacker_states: Vec<LegacyBackerState>, milestone_states: Vec<LegacyMilestone>| LegacyProjectState{
+        project_company: String::from("company"),
+        project_title: format!("demo{}", id),
+        project_description: String::from("demo"),
+        project_ecosystem: String::from("terra"),
+        project_createddate: String::from("20211223"),
+        project_saft: String::from("https://demo/saft"),
+        project_logo: String::from("https://demo/icon"),
+        project_whitepaper: String::from("https://demo/whitepaper"),
+        project_website: String::from("https://demo"),
+        project_email: String::from("demo@gmail.com"),
+        project_id: Uint128::new(id),
+        creator_wallet: Addr::unchecked("creator"),
+        project_collected: Uint128::new(300),
+        project_status: status,
+        fundraising_stage: Uint128::zero(),
+        backerbacked_amount: backer_states.iter().map(|backer| backer.ust_amount.amount).sum(),
+        communitybacked_amount: communitybacker_states.iter().map(|backer| backer.ust_amount.amount).sum(),
+        backer_states,
+        communitybacker_states,
+        project_milestonestep: Uint128::new(milestone_states.iter().filter(|m| m.milestone_status == Uint128::new(2)).count() as u128),
+        milestone_states,
+        teammember_states: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: Addr::unchecked(""),
+    };
+    let votes = vec![
+        LegacyVote{ wallet: Addr::unchecked("backer1"), voted: true },
+        LegacyVote{ wallet: Addr::unchecked("admin"), voted: true },
+    ];
+    let projects = [
+        project(1, ProjectStatus::Releasing,
+            vec![backing("backer1", 100000000), backing("backer1", 50000000)],
+            vec![backing("community1", 150000000)],
+            vec![milestone(0, 100, 2, votes.clone()), milestone(1, 200, 0, votes)]),
+        project(2, ProjectStatus::Fundraising, vec![backing("backer2", 50000000)], Vec::new(), Vec::new()),
+        project(3, ProjectStatus::Fail, vec![backing("backer3", 10000000)], Vec::new(), Vec::new()),
+        project(4, ProjectStatus::Fail, vec![backing("backer4", 40000000)],
+            vec![backing("community4", 60000000)], Vec::new()),
+        project(5, ProjectStatus::WefundVote, Vec::new(), Vec::new(), Vec::new()),
+    ];
+    for x in projects.iter() {
+        LEGACY_PROJECTSTATES.save(deps.as_mut().storage, x.project_id.u128().into(), x).unwrap();
+    }
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg{}).unwrap();
+    assert_eq!(res.attributes[1].value, "5".to_string());
+//anchor addresses became yield strategy, fee config seeded, uusd held less community refunds is fees
+    let config:Config = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetConfig{}).unwrap()).unwrap();
+    assert_eq!(config.yield_strategy, YieldStrategy::Anchor{
+        market: Addr::unchecked("market"),
+        aust_token: Addr::unchecked("aust"),
+    });
+    assert_eq!(config.timelock, Uint128::new(172800));
+    let res:FeeConfig = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetFeeConfig{}).unwrap()).unwrap();
+    assert_eq!(res, FeeConfig::default());
+    let res:Vec<Asset> = from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::GetAccruedFees{}).unwrap()).unwrap();
+    assert_eq!(res, vec![Asset{ info: AssetInfo::uusd(), amount: Uint128::new(12000000) }]);
+//backings of a wallet summed up, aust split by principal left
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.metadata.title(), "demo1");
+    assert_eq!(project.project.yield_strategy, Some(config.yield_strategy.clone()));
+    assert_eq!(project.project.escrow_principal, Uint128::new(200000000));
+    assert_eq!(project.project.escrow_shares, Uint128::new(160000000));
+    assert_eq!(project.project.milestone_count, Uint128::new(2));
+    assert_eq!(project.project.backer_count, Uint128::new(1));
+    assert_eq!(project.backer_states[0].ust_amount.amount, Uint128::new(150000000));
+    assert_eq!(project.backer_states[0].fee_amount, Uint128::new(10000000));
+    assert!(project.communitybacker_states[0].community);
+//released milestone kept, vote in progress reopened for all backers
+    assert_eq!(project.milestone_states[0].milestone_status, Uint128::new(2));
+    assert_eq!(project.milestone_states[0].milestone_votes[0].voted, Some(true));
+    let voting = &project.milestone_states[1];
+    assert_eq!(voting.milestone_votes.len(), 2);
+    assert!(voting.milestone_votes.iter().all(|vote| vote.voted.is_none()));
+    assert_eq!(voting.milestone_voteend, Uint128::from(mock_env().block.time.seconds() as u128 + DEFAULT_VOTE_PERIOD));
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(2) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.escrow_shares, Uint128::new(40000000));
+    assert_eq!(project.backer_states[0].fee_amount, Uint128::new(5000000));
+//failed project was refunded when it failed
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(3) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.escrow_shares, Uint128::zero());
+    assert!(project.backer_states[0].refunded);
+//community share of failed project was left in contract, still claimable
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(4) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(project.backer_states[0].refunded);
+    assert!(!project.communitybacker_states[0].refunded);
+    assert_eq!(project.project.held_assets, vec![Asset{ info: AssetInfo::uusd(), amount: Uint128::new(60000000) }]);
+    let msg = ExecuteMsg::ClaimRefund{ project_id: Uint128::new(4) };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("backer4", &[]), msg.clone()).unwrap_err();
+    assert!(matches!(err, ContractError::NothingToRefund{}));
+    let res = execute(deps.as_mut(), mock_env(), mock_info("community4", &[]), msg).unwrap();
+    assert_eq!(res.messages[0].msg, CosmosMsg::Bank(BankMsg::Send{
+        to_address: String::from("community4"),
+        amount: vec![Coin::new(60000000, "uusd")],
+    }));
+//project waiting for listing gets a community vote
+    let msg = QueryMsg::GetListingVote{ project_id: Uint128::new(5) };
+    let vote:Option<ListingVote> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(vote.unwrap().snapshot_height, mock_env().block.height);
+//second run finds nothing to do
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg{}).unwrap();
+    assert_eq!(res.attributes[1].value, "0".to_string());
+}
+
+#[test]
+fn listing_vote(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    for member in ["community1", "community2", "community3"] {
+        let msg = ExecuteMsg::AddCommunitymember{ wallet: String::from(member) };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+    let add_project = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), add_project.clone()).unwrap();
+    execute(deps.as_mut(), mock_env(), info.clone(), add_project.clone()).unwrap();
+
+    let cast = |project_id: u128, voted: bool| ExecuteMsg::CastListingVote{
+        project_id: Uint128::new(project_id),
+        voted,
+    };
+//only community members vote
+    let res = execute(deps.as_mut(), mock_env(), mock_info("outsider", &[]), cast(1, true));
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), mock_info("community1", &[]), cast(1, true)).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("community2", &[]), cast(1, true)).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("community1", &[]), cast(2, false)).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("community2", &[]), cast(2, false)).unwrap();
+    execute(deps.as_mut(), mock_env(), mock_info("community3", &[]), cast(2, true)).unwrap();
+//membership changes after opening don't move the vote
+    let msg = ExecuteMsg::AddCommunitymember{ wallet: String::from("community4") };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::RemoveCommunitymember{ wallet: String::from("community3") };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), mock_info("community4", &[]), cast(1, false)).unwrap_err();
+    assert!(matches!(err, ContractError::NotEligibleVoter{}));
+    execute(deps.as_mut(), mock_env(), mock_info("community3", &[]), cast(2, true)).unwrap();
+//tally after vote period only
+    let tally = |project_id: u128| ExecuteMsg::TallyListingVote{ project_id: Uint128::new(project_id) };
+    assert!(execute(deps.as_mut(), mock_env(), mock_info("anyone", &[]), tally(1)).is_err());
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(DEFAULT_VOTE_PERIOD as u64 + 1);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("community3", &[]), cast(1, false));
+    assert!(res.is_err());
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), tally(1)).unwrap();
+    execute(deps.as_mut(), env.clone(), mock_info("anyone", &[]), tally(2)).unwrap();
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::Fundraising);
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(2) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::Rejected);
+
+    let msg = QueryMsg::GetListingVote{ project_id: Uint128::new(2) };
+    let vote:Option<ListingVote> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    let vote = vote.unwrap();
+    assert_eq!(vote.passed, Some(false));
+    assert_eq!(vote.community, vec![Addr::unchecked("community1"), Addr::unchecked("community2"), Addr::unchecked("community3")]);
+//listing parameters are admin only
+    let set_voting = ExecuteMsg::SetListingVoting{ listing_voting: ListingVoting{
+        quorum: Uint128::new(101),
+        ..ListingVoting::default()
+    }};
+    assert!(execute(deps.as_mut(), mock_env(), info.clone(), set_voting).is_err());
+    let set_voting = ExecuteMsg::SetListingVoting{ listing_voting: ListingVoting::default() };
+    assert!(execute(deps.as_mut(), mock_env(), mock_info("community1", &[]), set_voting.clone()).is_err());
+    execute(deps.as_mut(), mock_env(), info.clone(), set_voting).unwrap();
+//staking weight is read as of the block the vote opened
+    let set_voting = ExecuteMsg::SetListingVoting{ listing_voting: ListingVoting{
+        weight_source: WeightSource::Staking{ contract: Addr::unchecked("staking") },
+        ..ListingVoting::default()
+    }};
+    execute(deps.as_mut(), mock_env(), info.clone(), set_voting).unwrap();
+    let opened = mock_env().block.height;
+    deps.querier.with_stake("staker1", 1, Uint128::new(300));
+    deps.querier.with_stake("staker2", 1, Uint128::new(100));
+    execute(deps.as_mut(), mock_env(), info, add_project).unwrap();
+//staker1 moves its stake to staker3 after opening
+    deps.querier.with_stake("staker1", opened + 1, Uint128::zero());
+    deps.querier.with_stake("staker3", opened + 1, Uint128::new(300));
+
+    let mut env = mock_env();
+    env.block.height = opened + 5;
+    let err = execute(deps.as_mut(), env.clone(), mock_info("staker3", &[]), cast(3, true)).unwrap_err();
+    assert!(matches!(err, ContractError::NotEligibleVoter{}));
+    let res = execute(deps.as_mut(), env.clone(), mock_info("staker1", &[]), cast(3, true)).unwrap();
+    assert_eq!(res.attributes[1].value, "300".to_string());
+//300 of 400 staked at opening voted, restaked tokens not counted
+    env.block.time = env.block.time.plus_seconds(DEFAULT_VOTE_PERIOD as u64 + 1);
+    execute(deps.as_mut(), env, mock_info("anyone", &[]), tally(3)).unwrap();
+
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(3) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::Fundraising);
+    let msg = QueryMsg::GetListingVote{ project_id: Uint128::new(3) };
+    let vote:Option<ListingVote> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(vote.unwrap().snapshot_height, opened);
+}
+
+#[test]
+fn status_transitions(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    let add_project = msg.clone();
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let set_status = |status: ProjectStatus| ExecuteMsg::SetProjectStatus{
+        project_id: Uint128::new(1),
+        status,
+    };
+//no jump past fundraising
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), set_status(ProjectStatus::Done)).unwrap_err();
+    assert!(matches!(err, ContractError::InvalidTransition{
+        from: ProjectStatus::WefundVote,
+        to: ProjectStatus::Done
+    }));
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    execute(deps.as_mut(), mock_env(), info.clone(), set_status(ProjectStatus::Fail)).unwrap();
+//failed project stays failed
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), set_status(ProjectStatus::Fundraising));
+    assert!(res.is_err());
+
+    let msg = QueryMsg::GetProjectHistory{ project_id: Uint128::new(1) };
+    let history:Vec<StatusTransition> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(history.len(), 2);
+    assert_eq!(history[0].from, ProjectStatus::WefundVote);
+    assert_eq!(history[0].to, ProjectStatus::Fundraising);
+    assert_eq!(history[1].to, ProjectStatus::Fail);
+    assert_eq!(history[1].timestamp, Uint128::from(mock_env().block.time.seconds()));
+//listing by status still waits for review
+    let submit = ExecuteMsg::SubmitProject{
+        project_metadata: metadata(),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: Vec::new(),
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("creator", &[Coin::new(100000000, "uusd")]), submit).unwrap();
+    let set_status = |project_id: u128, status: ProjectStatus| ExecuteMsg::SetProjectStatus{
+        project_id: Uint128::new(project_id),
+        status,
+    };
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), set_status(2, ProjectStatus::Fundraising)).unwrap_err();
+    assert!(matches!(err, ContractError::UnderReview{}));
+//and closes the listing vote like approve
+    execute(deps.as_mut(), mock_env(), info.clone(), add_project).unwrap();
+    execute(deps.as_mut(), mock_env(), info.clone(), set_status(3, ProjectStatus::Fundraising)).unwrap();
+    let msg = QueryMsg::GetListingVote{ project_id: Uint128::new(3) };
+    let vote:Option<ListingVote> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert!(vote.is_none());
+//not done while escrow is held
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(3),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(105000000, "uusd")]), msg).unwrap();
+    execute(deps.as_mut(), mock_env(), info.clone(), set_status(3, ProjectStatus::Releasing)).unwrap();
+    let err = execute(deps.as_mut(), mock_env(), info.clone(), set_status(3, ProjectStatus::Done)).unwrap_err();
+    assert!(matches!(err, ContractError::EscrowNotEmpty{}));
+//completing pays out and records done
+    let msg = ExecuteMsg::CompleteProject{ project_id: Uint128::new(3) };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = QueryMsg::GetProjectHistory{ project_id: Uint128::new(3) };
+    let history:Vec<StatusTransition> = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(history.last().unwrap().from, ProjectStatus::Releasing);
+    assert_eq!(history.last().unwrap().to, ProjectStatus::Done);
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(3) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.project_status, ProjectStatus::Done);
+}
+
+#[test]
+fn fundraising_rounds(){
+    let mut deps = mock_dependencies(&[]);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: None,
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let stage = |title: &str, price: u128| VestingParameter{
+        stage_title: String::from(title),
+        stage_price: Uint128::new(price),
+        stage_amount: Uint128::new(1000),
+        stage_soon: Uint128::zero(),
+        stage_after: Uint128::zero(),
+        stage_period: Uint128::new(100),
+    };
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: vec![stage("seed", 10000), stage("presale", 20000)],
+        token_addr: "".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let now = mock_env().block.time.seconds() as u128;
+    let round = |start: u128, end: u128, cap: u128| FundraisingRound{
+        start: Uint128::new(start),
+        end: Uint128::new(end),
+        cap: Uint128::new(cap),
+        raised: Uint128::zero(),
+    };
+//one round per vesting stage, not overlapping
+    let set_rounds = |rounds: Vec<FundraisingRound>| ExecuteMsg::SetFundraisingRounds{
+        project_id: Uint128::new(1),
+        rounds,
+    };
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), set_rounds(vec![round(now, now + 100, 100000000)]));
+    assert!(res.is_err());
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), set_rounds(vec![
+        round(now, now + 100, 100000000), round(now + 50, now + 1000, 300000000)]));
+    assert!(res.is_err());
+    execute(deps.as_mut(), mock_env(), info.clone(), set_rounds(vec![
+        round(now, now + 100, 100000000), round(now + 200, now + 1000, 300000000)])).unwrap();
+
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let back = |wallet: &str, stage: u128| ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from(wallet),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::new(stage),
+    };
+//only active round takes contributions
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(105000000, "uusd")]), back("backer1", 1));
+    assert!(res.is_err());
+//filled round moves to next, excess returned
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(210000000, "uusd")]), back("backer1", 0)).unwrap();
+    assert_eq!(res.attributes[1].value, "100000000".to_string());
+    assert_eq!(res.attributes[2].value, "105000000".to_string());
+
+    let msg = QueryMsg::GetFundraisingRound{ project_id: Uint128::new(1) };
+    let res:FundraisingRoundResponse = from_binary(&query(deps.as_ref(), mock_env(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.stage, Uint128::new(1));
+    assert_eq!(res.stage_title, String::from("presale"));
+    assert_eq!(res.stage_price, Uint128::new(20000));
+    assert_eq!(res.round.unwrap().raised, Uint128::zero());
+//next round not open yet
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer2", &[Coin::new(105000000, "uusd")]), back("backer2", 1));
+    assert!(res.is_err());
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(300);
+    execute(deps.as_mut(), env.clone(), mock_info("backer2", &[Coin::new(105000000, "uusd")]), back("backer2", 1)).unwrap();
+    let res:FundraisingRoundResponse = from_binary(&query(deps.as_ref(), env.clone(), msg.clone()).unwrap()).unwrap();
+    assert_eq!(res.round.unwrap().raised, Uint128::new(100000000));
+//all rounds over
+    env.block.time = env.block.time.plus_seconds(1000);
+    let res = execute(deps.as_mut(), env.clone(), mock_info("backer3", &[Coin::new(105000000, "uusd")]), back("backer3", 1));
+    assert!(res.is_err());
+    let res:FundraisingRoundResponse = from_binary(&query(deps.as_ref(), env, msg).unwrap()).unwrap();
+    assert_eq!(res.round, None);
+}
+
+#[test]
+fn token_allocation(){
+    let mut deps = mock_dependencies(&[]);
+    deps.querier.with_token_decimals(&"token1".to_string(), 6);
+
+    let msg = InstantiateMsg{
+        admin: Some(String::from("admin")),
+        wefund: Some(String::from("Wefund")),
+        yield_strategy: None,
+        vesting_contract: Some(String::from("vesting")),
+        timelock: None
+    };
+    let info = mock_info("admin", &[]);
+    instantiate(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+//0.03 uusd per token, 5000 tokens in stage
+    let stage = |price: u128| VestingParameter{
+        stage_title: String::from("seed"),
+        stage_price: Uint128::new(price),
+        stage_amount: Uint128::new(5000),
+        stage_soon: Uint128::zero(),
+        stage_after: Uint128::zero(),
+        stage_period: Uint128::new(100),
+    };
+    let add_project = |price: u128| ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: vec![stage(price)],
+        token_addr: "token1".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    assert!(execute(deps.as_mut(), mock_env(), info.clone(), add_project(0)).is_err());
+    execute(deps.as_mut(), mock_env(), info.clone(), add_project(30000)).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(1), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+
+    let back = |wallet: &str, stage: u128| ExecuteMsg::Back2Project{
+        project_id: Uint128::new(1),
+        backer_wallet: String::from(wallet),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::new(stage),
+    };
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(105000000, "uusd")]), back("backer1", 1));
+    assert!(res.is_err());
+//net contribution over price, rounded down
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(105000000, "uusd")]), back("backer1", 0)).unwrap();
+    assert_eq!(res.attributes[3].value, "3333333333".to_string());
+//only what tokens left cover accepted, excess returned
+    let res = execute(deps.as_mut(), mock_env(), mock_info("relayer", &[Coin::new(105000000, "uusd")]), back("backer2", 0)).unwrap();
+    assert_eq!(res.attributes[1].value, "50000000".to_string());
+    assert_eq!(res.attributes[3].value, "1666666666".to_string());
+//tokens vest to backer wallet, not to whoever sent the funds
+    assert_eq!(res.messages.last().unwrap().msg, CosmosMsg::Wasm(WasmMsg::Execute{
+        contract_addr: String::from("vesting"),
+        msg: to_binary(&VestingMsg::AddUser{
+            project_id: Uint128::new(1),
+            wallet: Addr::unchecked("backer2"),
+            stage: Uint128::zero(),
+            amount: Uint128::new(1666666666),
+        }).unwrap(),
+        funds: vec![],
+    }));
+
+    let msg = QueryMsg::GetContributions{ project_id: Uint128::new(1), wallet: String::from("backer2") };
+    let res:ContributionsResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(res.position.token_amount, Uint128::new(1666666666));
+    assert_eq!(res.contributions[0].token_amount, Uint128::new(1666666666));
+//stage sold out
+    let res = execute(deps.as_mut(), mock_env(), mock_info("backer3", &[Coin::new(105000000, "uusd")]), back("backer3", 0));
+    assert!(res.is_err());
+    let msg = QueryMsg::GetProject{ project_id: Uint128::new(1) };
+    let project:ProjectResponse = from_binary(&query(deps.as_ref(), mock_env(), msg).unwrap()).unwrap();
+    assert_eq!(project.project.stage_allocated, vec![Uint128::new(4999999999)]);
+//cheaper later stage can't be picked while project is at first one
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: vec![stage(30000), stage(10000)],
+        token_addr: "token1".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(2), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(2),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::new(1),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(105000000, "uusd")]), msg).unwrap_err();
+    assert!(matches!(err, ContractError::NotActiveRound{ stage } if stage == Uint128::zero()));
+//stage too large to count in base units is an error, not a panic
+    let msg = ExecuteMsg::AddProject{
+        project_metadata: metadata(),
+        creator_wallet: String::from("creator"),
+        project_collected: Uint128::new(1000),
+        project_milestones: Vec::new(),
+        project_teammembers: Vec::new(),
+        vesting: vec![VestingParameter{ stage_amount: Uint128::MAX, ..stage(30000) }],
+        token_addr: "token1".to_string(),
+        fundraising_deadline: None,
+        milestone_voting: None,
+        accepted_assets: None,
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    let msg = ExecuteMsg::WefundApprove{ project_id: Uint128::new(3), fee_bps: None };
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    let msg = ExecuteMsg::Back2Project{
+        project_id: Uint128::new(3),
+        backer_wallet: String::from("backer1"),
+        otherchain: "ethereum".to_string(),
+        otherchain_wallet: "ether_wallet".to_string(),
+        fundraising_stage: Uint128::zero(),
+    };
+    let err = execute(deps.as_mut(), mock_env(), mock_info("backer1", &[Coin::new(105000000, "uusd")]), msg).unwrap_err();
+    assert!(matches!(err, ContractError::Std(StdError::Overflow{..})));
+}
+
+#[test]
+fn yield_adapters(){
+    let mut deps = mock_dependencies(&[]);
+
+//hold: one share is one uusd, nothing to send
+    let hold = HoldAdapter{};
+    assert!(hold.deposit(Coin::new(1000000, "uusd")).unwrap().is_empty());
+    assert_eq!(hold.deposit_shares(&deps.as_ref().querier, 1000000).unwrap(), Uint128::new(1000000));
+    let withdrawal = hold.withdraw(&deps.as_ref().querier, 400000, Uint128::new(1000000)).unwrap();
+    assert_eq!(withdrawal.shares, Uint128::new(400000));
+    assert_eq!(withdrawal.amount, Uint128::new(400000));
+    assert!(withdrawal.msgs.is_empty());
+
+//anchor at 1.25: shares round down on deposit, up on withdraw
+    deps.querier.with_exchange_rate(Decimal256::percent(125));
+    let anchor = AnchorAdapter{ market: Addr::unchecked("market"), aust_token: Addr::unchecked("aust") };
+    assert_eq!(anchor.deposit_shares(&deps.as_ref().querier, 1000001).unwrap(), Uint128::new(800000));
+    let withdrawal = anchor.withdraw(&deps.as_ref().querier, 1000001, Uint128::new(10000000)).unwrap();
+    assert_eq!(withdrawal.shares, Uint128::new(800001));
+    assert_eq!(withdrawal.amount, Uint128::new(1000001));
+    assert_eq!(withdrawal.msgs[0], CosmosMsg::Wasm(WasmMsg::Execute{
+        contract_addr: "aust".to_string(),
+        msg: to_binary(&Cw20ExecuteMsg::Send{
+            contract: "market".to_string(),
+            msg: to_binary(&crate::market::Cw20HookMsg::RedeemStable{}).unwrap(),
+            amount: Uint128::new(800001),
+        }).unwrap(),
+        funds: Vec::new(),
+    }));
+//never past the escrow
+    let withdrawal = anchor.withdraw(&deps.as_ref().querier, 1000001, Uint128::new(500000)).unwrap();
+    assert_eq!(withdrawal.shares, Uint128::new(500000));
+    assert_eq!(withdrawal.amount, Uint128::new(625000));
+
+//vault at 2: deposit then redeem returns the deposit
+    deps.querier.with_vault_rate(Decimal::percent(200));
+    let vault = VaultAdapter{ vault: Addr::unchecked("vault"), share_token: Addr::unchecked("share") };
+    let shares = vault.deposit_shares(&deps.as_ref().querier, 3000000).unwrap();
+    assert_eq!(shares, Uint128::new(1500000));
+    let withdrawal = vault.withdraw_shares(&deps.as_ref().querier, shares).unwrap();
+    assert_eq!(withdrawal.amount, Uint128::new(3000000));
+
+//zero rate is an error, not a panic
+    deps.querier.with_vault_rate(Decimal::zero());
+    let err = vault.deposit_shares(&deps.as_ref().querier, 1000000).unwrap_err();
+    assert!(matches!(err, ContractError::ZeroExchangeRate{}));
+    assert!(vault.withdraw(&deps.as_ref().querier, 1000000, Uint128::new(1000000)).is_err());
+}
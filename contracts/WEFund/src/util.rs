@@ -0,0 +1,27 @@
+use crate::error::ContractError;
+
+use cosmwasm_std::{ Storage, Addr, Response };
+
+use crate::state::{ CONFIG, ROLES, Role };
+
+pub fn check_onlyowner(storage: &dyn Storage, sender: Addr) -> Result<Response, ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.owner != sender {
+        return Err(ContractError::Unauthorized{});
+    }
+    Ok(Response::new())
+}
+
+//------------owner and admins pass every role check------------------
+pub fn check_role(storage: &dyn Storage, sender: Addr, role: Role) -> Result<Response, ContractError> {
+    let config = CONFIG.load(storage)?;
+    if config.owner == sender {
+        return Ok(Response::new());
+    }
+
+    let roles = ROLES.may_load(storage, sender)?.unwrap_or_default();
+    if !roles.contains(&Role::Admin) && !roles.contains(&role) {
+        return Err(ContractError::Unauthorized{});
+    }
+    Ok(Response::new())
+}
@@ -0,0 +1,56 @@
+use cosmwasm_std::{Addr, Deps, StdResult, Uint128};
+
+use crate::state::{ListingVote, WeightSource};
+use crate::staking::QueryMsg as StakingQuery;
+
+pub trait VoteWeight{
+    //------weight `wallet` votes with as of vote opening, zero when not eligible-------
+    fn weight(&self, deps: Deps, wallet: &Addr, vote: &ListingVote) -> StdResult<Uint128>;
+
+    //------weight of every eligible wallet as of vote opening, base of the quorum-----
+    fn total_weight(&self, deps: Deps, vote: &ListingVote) -> StdResult<Uint128>;
+}
+
+//------------one vote per community member when vote opened----------------
+pub struct CommunityWeight{}
+
+impl VoteWeight for CommunityWeight{
+    fn weight(&self, _deps: Deps, wallet: &Addr, vote: &ListingVote) -> StdResult<Uint128> {
+        Ok(Uint128::new(vote.community.contains(wallet) as u128))
+    }
+
+    fn total_weight(&self, _deps: Deps, vote: &ListingVote) -> StdResult<Uint128> {
+        Ok(Uint128::new(vote.community.len() as u128))
+    }
+}
+
+//------------tokens staked in staking contract----------------------
+pub struct StakingWeight{
+    pub contract: Addr,
+}
+
+impl VoteWeight for StakingWeight{
+    //------stake at vote opening, moving tokens later adds no weight----------
+    fn weight(&self, deps: Deps, wallet: &Addr, vote: &ListingVote) -> StdResult<Uint128> {
+        deps.querier.query_wasm_smart(
+            self.contract.to_string(),
+            &StakingQuery::GetStakeAt{ wallet: wallet.clone(), height: vote.snapshot_height }
+        )
+    }
+
+    //------staked principal only, reward pool held by contract not counted------
+    fn total_weight(&self, deps: Deps, vote: &ListingVote) -> StdResult<Uint128> {
+        deps.querier.query_wasm_smart(
+            self.contract.to_string(),
+            &StakingQuery::GetTotalStakeAt{ height: vote.snapshot_height }
+        )
+    }
+}
+
+pub fn get_weight_source(source: &WeightSource) -> Box<dyn VoteWeight>
+{
+    match source {
+        WeightSource::Community => Box::new(CommunityWeight{}),
+        WeightSource::Staking{ contract } => Box::new(StakingWeight{ contract: contract.clone() }),
+    }
+}
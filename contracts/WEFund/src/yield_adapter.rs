@@ -0,0 +1,272 @@
+use cosmwasm_std::{
+    Addr, to_binary, Api, Coin, CosmosMsg, QuerierWrapper, StdResult,
+    Uint128, WasmMsg
+};
+use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, BalanceResponse as Cw20BalanceResponse};
+
+use crate::error::ContractError;
+use crate::state::{Config, ProjectState, YieldStrategy};
+use crate::market::{ExecuteMsg as AnchorMarket, Cw20HookMsg as AnchorHookMsg,
+    QueryMsg as AnchorQuery, EpochStateResponse};
+use crate::vault::{ExecuteMsg as VaultMsg, Cw20HookMsg as VaultHookMsg,
+    QueryMsg as VaultQuery, ExchangeRateResponse};
+
+pub const UST: u128 = 1000000; //ust unit, also scale of exchange rates
+
+//------------shares to redeem for a release------------------------
+pub struct Withdrawal{
+    pub shares: Uint128,
+    pub amount: Uint128, //uusd the redeemed shares return
+    pub exchange_rate: u128,
+    pub msgs: Vec<CosmosMsg>,
+}
+
+pub trait YieldAdapter{
+    //------messages parking `fund` with the strategy-----------------
+    fn deposit(&self, fund: Coin) -> StdResult<Vec<CosmosMsg>>;
+
+    //------messages turning `shares` back into uusd for this contract-----
+    fn redeem(&self, shares: Uint128) -> StdResult<Vec<CosmosMsg>>;
+
+    //------uusd value of one share unit, scaled by UST-------------
+    fn exchange_rate(&self, querier: &QuerierWrapper) -> StdResult<u128>;
+
+    //------share units held by `holder`----------------------------
+    fn share_balance(&self, querier: &QuerierWrapper, holder: &Addr) -> StdResult<Uint128>;
+
+    //------cw20 share token, None when shares are plain uusd------
+    fn share_token(&self) -> Option<Addr>;
+
+    //------exchange rate, shares can't be priced at zero-----------
+    fn checked_exchange_rate(&self, querier: &QuerierWrapper) -> Result<u128, ContractError>
+    {
+        match self.exchange_rate(querier)? {
+            0 => Err(ContractError::ZeroExchangeRate{}),
+            exchange_rate => Ok(exchange_rate),
+        }
+    }
+
+    //------share units minted for depositing `amount` uusd-----------
+    fn deposit_shares(&self, querier: &QuerierWrapper, amount: u128) -> Result<Uint128, ContractError>
+    {
+        let exchange_rate = self.checked_exchange_rate(querier)?;
+        Ok(Uint128::new(amount * UST / exchange_rate))
+    }
+
+    //------redeem `shares` units of a project escrow-----------------
+    fn withdraw_shares(&self, querier: &QuerierWrapper, shares: Uint128) -> StdResult<Withdrawal>
+    {
+        let exchange_rate = self.exchange_rate(querier)?;
+        let amount = shares.u128() * exchange_rate / UST;
+
+        Ok(Withdrawal{
+            shares,
+            amount: Uint128::new(amount),
+            exchange_rate,
+            msgs: self.redeem(shares)?,
+        })
+    }
+
+    //------redeem enough of `escrow_shares` to return `amount` uusd----------
+    fn withdraw(&self, querier: &QuerierWrapper, amount: u128, escrow_shares: Uint128)
+        -> Result<Withdrawal, ContractError>
+    {
+        let exchange_rate = self.checked_exchange_rate(querier)?;
+
+        //-------round up so the release is fully covered, never past the escrow-----
+        let shares = std::cmp::min((amount * UST).div_ceil(exchange_rate), escrow_shares.u128());
+        Ok(self.withdraw_shares(querier, Uint128::new(shares))?)
+    }
+}
+
+//------------Anchor compatible money market-----------------------
+pub struct AnchorAdapter{
+    pub market: Addr,
+    pub aust_token: Addr,
+}
+
+impl YieldAdapter for AnchorAdapter{
+    fn deposit(&self, fund: Coin) -> StdResult<Vec<CosmosMsg>> {
+        let deposit = WasmMsg::Execute {
+            contract_addr: self.market.to_string(),
+            msg: to_binary(&AnchorMarket::DepositStable {})?,
+            funds: vec![fund]
+        };
+        Ok(vec![CosmosMsg::Wasm(deposit)])
+    }
+
+    fn redeem(&self, shares: Uint128) -> StdResult<Vec<CosmosMsg>> {
+        //----ask aust_token for transfer to anchor market and execute redeem_stable ----------
+        let withdraw = WasmMsg::Execute {
+            contract_addr: self.aust_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: self.market.to_string(),
+                msg: to_binary(&AnchorHookMsg::RedeemStable{})?,
+                amount: shares
+            })?,
+            funds: Vec::new()
+        };
+        Ok(vec![CosmosMsg::Wasm(withdraw)])
+    }
+
+    fn exchange_rate(&self, querier: &QuerierWrapper) -> StdResult<u128> {
+        let epoch: EpochStateResponse = querier.query_wasm_smart(
+            self.market.to_string(),
+            &AnchorQuery::EpochState{
+                block_height: None,
+                distributed_interest: None,
+            }
+        )?;
+        Ok(convert_str_int(epoch.exchange_rate.to_string()))
+    }
+
+    fn share_balance(&self, querier: &QuerierWrapper, holder: &Addr) -> StdResult<Uint128> {
+        query_cw20_balance(querier, &self.aust_token, holder)
+    }
+
+    fn share_token(&self) -> Option<Addr> {
+        Some(self.aust_token.clone())
+    }
+}
+
+//------------Vault minting cw20 share tokens-------------------------
+pub struct VaultAdapter{
+    pub vault: Addr,
+    pub share_token: Addr,
+}
+
+impl YieldAdapter for VaultAdapter{
+    fn deposit(&self, fund: Coin) -> StdResult<Vec<CosmosMsg>> {
+        let deposit = WasmMsg::Execute {
+            contract_addr: self.vault.to_string(),
+            msg: to_binary(&VaultMsg::Deposit {})?,
+            funds: vec![fund]
+        };
+        Ok(vec![CosmosMsg::Wasm(deposit)])
+    }
+
+    fn redeem(&self, shares: Uint128) -> StdResult<Vec<CosmosMsg>> {
+        let withdraw = WasmMsg::Execute {
+            contract_addr: self.share_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: self.vault.to_string(),
+                msg: to_binary(&VaultHookMsg::Redeem{})?,
+                amount: shares
+            })?,
+            funds: Vec::new()
+        };
+        Ok(vec![CosmosMsg::Wasm(withdraw)])
+    }
+
+    fn exchange_rate(&self, querier: &QuerierWrapper) -> StdResult<u128> {
+        let rate: ExchangeRateResponse = querier.query_wasm_smart(
+            self.vault.to_string(),
+            &VaultQuery::ExchangeRate{}
+        )?;
+        Ok((Uint128::new(UST) * rate.exchange_rate).u128())
+    }
+
+    fn share_balance(&self, querier: &QuerierWrapper, holder: &Addr) -> StdResult<Uint128> {
+        query_cw20_balance(querier, &self.share_token, holder)
+    }
+
+    fn share_token(&self) -> Option<Addr> {
+        Some(self.share_token.clone())
+    }
+}
+
+//------------Hold in contract, one share is one uusd-------------------
+pub struct HoldAdapter{}
+
+impl YieldAdapter for HoldAdapter{
+    fn deposit(&self, _fund: Coin) -> StdResult<Vec<CosmosMsg>> {
+        Ok(Vec::new())
+    }
+
+    fn redeem(&self, _shares: Uint128) -> StdResult<Vec<CosmosMsg>> {
+        Ok(Vec::new())
+    }
+
+    fn exchange_rate(&self, _querier: &QuerierWrapper) -> StdResult<u128> {
+        Ok(UST)
+    }
+
+    fn share_balance(&self, querier: &QuerierWrapper, holder: &Addr) -> StdResult<Uint128> {
+        Ok(querier.query_balance(holder.to_string(), "uusd")?.amount)
+    }
+
+    fn share_token(&self) -> Option<Addr> {
+        None
+    }
+}
+
+pub fn get_adapter(strategy: &YieldStrategy) -> Box<dyn YieldAdapter>
+{
+    match strategy.clone() {
+        YieldStrategy::Anchor{ market, aust_token } =>
+            Box::new(AnchorAdapter{ market, aust_token }),
+        YieldStrategy::Vault{ vault, share_token } =>
+            Box::new(VaultAdapter{ vault, share_token }),
+        YieldStrategy::Hold =>
+            Box::new(HoldAdapter{}),
+    }
+}
+
+//------------strategy in effect for a project, fixed once it holds escrow------------
+pub fn project_strategy(config: &Config, x: &ProjectState) -> YieldStrategy
+{
+    x.yield_strategy.clone().unwrap_or(config.yield_strategy.clone())
+}
+
+pub fn validate_strategy(api: &dyn Api, strategy: YieldStrategy) -> StdResult<YieldStrategy>
+{
+    match strategy {
+        YieldStrategy::Anchor{ market, aust_token } => Ok(YieldStrategy::Anchor{
+            market: api.addr_validate(market.as_str())?,
+            aust_token: api.addr_validate(aust_token.as_str())?,
+        }),
+        YieldStrategy::Vault{ vault, share_token } => Ok(YieldStrategy::Vault{
+            vault: api.addr_validate(vault.as_str())?,
+            share_token: api.addr_validate(share_token.as_str())?,
+        }),
+        YieldStrategy::Hold => Ok(YieldStrategy::Hold),
+    }
+}
+
+fn query_cw20_balance(querier: &QuerierWrapper, token: &Addr, holder: &Addr) -> StdResult<Uint128>
+{
+    let balance: Cw20BalanceResponse = querier.query_wasm_smart(
+        token.to_string(),
+        &Cw20QueryMsg::Balance{
+            address: holder.to_string(),
+        }
+    )?;
+    Ok(balance.balance)
+}
+
+pub fn convert_str_int(str: String)
+    ->u128
+{
+    let bytes = str.into_bytes();
+    let mut res: u128 = 0;
+    let mut dot = false;
+    let mut dotbelow = 0;
+
+    for byte in bytes {
+        if byte < 48{
+            dot = true;
+        }
+        else if dotbelow < 6 {
+            res = res * 10 + (byte - 48) as u128;
+            if dot {
+                dotbelow += 1;
+            }
+        }
+    }
+    //-------scale short fractions like "1.25" to 6 decimals too-------
+    while dotbelow < 6 {
+        res *= 10;
+        dotbelow += 1;
+    }
+    res
+}